@@ -0,0 +1,29 @@
+extern crate vindinium_bot;
+use std::env;
+use vindinium_bot::opening;
+use vindinium_bot::opening::OpeningBook;
+use vindinium_bot::simulator::Simulator;
+
+// Usage: openings [book file] [first seed] [maps]
+// Searches an opening for every spawn of each simulated map and adds it to the book.
+
+fn main() {
+    let args = env::args().collect::<Vec<String>>();
+    let output = args.get(1).cloned().unwrap_or("openings.json".to_string());
+    let first = args.get(2).and_then(|arg| arg.parse().ok()).unwrap_or(1);
+    let maps = args.get(3).and_then(|arg| arg.parse().ok()).unwrap_or(4);
+
+    let mut book = OpeningBook::load(&output).unwrap_or(OpeningBook::new());
+    for seed in first..first + maps {
+        let sim = Simulator::new(seed);
+        for hero in &sim.game.heroes {
+            let line = opening::search(&sim.game, hero.id, opening::BOOK_TURNS);
+            println!("Map {}, hero {}: projected gold {}", seed, hero.id, line.score);
+            book.record(&sim.state_for(hero.id), line);
+        }
+    }
+    match book.save(&output) {
+        Ok(_) => println!("Saved {} openings to {}", book.len(), output),
+        Err(err) => panic!("{}", err)
+    }
+}
@@ -0,0 +1,72 @@
+extern crate vindinium_bot;
+use std::env;
+use vindinium_bot::evaluator::Evaluator;
+use vindinium_bot::training;
+use vindinium_bot::training::{TrainConfig, Version, Checkpoint};
+use vindinium_bot::ensemble;
+use vindinium_bot::ensemble::{EnsembleConfig, Arbitration, Gate};
+
+// Usage: train [checkpoint dir] [iterations] [games per version] [--evaluator]
+// Runs until done, or resumes from the last checkpoint when restarted.
+// With --gate, trains the gate of ensemble.json from iterations times games games instead.
+
+const STRATEGIES: usize = 2; // combat and the bot, as `EnsembleConfig::build` registers them
+
+fn main() {
+    let args = env::args().collect::<Vec<String>>();
+    let positional = args.iter().skip(1).filter(|arg| !arg.starts_with("--")).collect::<Vec<&String>>();
+    let mut config = TrainConfig::new();
+    match positional.get(0) {
+        Some(dir) => config.dir = dir.to_string(),
+        None => {}
+    }
+    match positional.get(1).and_then(|arg| arg.parse().ok()) {
+        Some(iterations) => config.iterations = iterations,
+        None => {}
+    }
+    match positional.get(2).and_then(|arg| arg.parse().ok()) {
+        Some(games) => config.games = games,
+        None => {}
+    }
+
+    if args.iter().any(|arg| arg == "--gate") {
+        let mut ensemble_config = EnsembleConfig::load("ensemble.json").unwrap_or(EnsembleConfig::new());
+        let mut gate = match ensemble_config.arbitration {
+            Arbitration::Gated(ref gate) => gate.clone(),
+            _ => Gate::new(STRATEGIES)
+        };
+        for iteration in 0..config.iterations {
+            let seeds = (0..config.games).map(|game| {
+                config.seed * 1000 + iteration * config.games + game
+            }).collect::<Vec<usize>>();
+            gate = ensemble::learn_gate(&ensemble_config, &gate, &seeds, config.turns);
+            println!("Gate trained on {} games", (iteration + 1) * config.games);
+        }
+        ensemble_config.arbitration = Arbitration::Gated(gate);
+        match ensemble_config.save("ensemble.json") {
+            Ok(_) => println!("Gate saved to ensemble.json"),
+            Err(err) => panic!("{}", err)
+        }
+        return;
+    }
+
+    let mut start = Version::new();
+    if args.iter().any(|arg| arg == "--evaluator") {
+        start.evaluator = Some(Evaluator::new());
+    }
+
+    let dir = config.dir.clone();
+    let progress = |checkpoint: &Checkpoint, candidate_score: f32, current_score: f32| {
+        println!("Checkpoint {}: score {:.3} vs {:.3}, saved to {}", checkpoint.number, candidate_score,
+                 current_score, Checkpoint::filename(&dir, checkpoint.number));
+    };
+    match training::train(&config, start, progress) {
+        Ok(checkpoints) => {
+            let best = checkpoints.iter()
+                                  .max_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(::std::cmp::Ordering::Equal))
+                                  .unwrap();
+            println!("Best checkpoint {} with score {:.3}", best.number, best.score);
+        },
+        Err(err) => panic!("{}", err)
+    }
+}
@@ -0,0 +1,33 @@
+extern crate vindinium_bot;
+use std::env;
+use vindinium_bot::tuning;
+use vindinium_bot::tuning::TuneConfig;
+
+// Usage: tune [output file] [generations] [population] [games per set]
+
+fn main() {
+    let args = env::args().collect::<Vec<String>>();
+    let output = args.get(1).cloned().unwrap_or("params.json".to_string());
+    let mut config = TuneConfig::new();
+    match args.get(2).and_then(|arg| arg.parse().ok()) {
+        Some(generations) => config.generations = generations,
+        None => {}
+    }
+    match args.get(3).and_then(|arg| arg.parse().ok()) {
+        Some(population) => config.population = population,
+        None => {}
+    }
+    match args.get(4).and_then(|arg| arg.parse().ok()) {
+        Some(games) => config.games = games,
+        None => {}
+    }
+
+    let best = tuning::evolve(&config, |generation, best| {
+        println!("Generation {}: best fitness {:.3}, {:?}", generation, best.fitness, best.params);
+    });
+    println!("Best fitness {:.3}: {:#?}", best.fitness, best.params);
+    match best.params.save(&output) {
+        Ok(_) => println!("Saved to {}", output),
+        Err(err) => panic!("{}", err)
+    }
+}
@@ -1,13 +1,38 @@
 use std::convert::From;
 use std::cmp::Ordering;
-use vindinium::{Dir, State, Tile, Hero};
+use vindinium::{Dir, State, Tile, Hero, HeroId};
 use pathing;
 use pathing::{UVector2, IVector2, Map};
+use combat;
+use spawn;
+use endgame;
+use endgame::Mode;
+use mines;
+use mines::MineLedger;
+use tavern;
+use route;
+use opponent::OpponentModel;
+use influence::InfluenceMap;
+use territory::Territory;
+use evade;
+use hunting;
+use safety;
+use stalemate::MotionTracker;
+use camping;
+use defense;
+use crashed;
+use elo;
+use params::Params;
+use evaluator::Evaluator;
+use env::{Policy, Observation};
+use opening;
+use opening::{OpeningBook, Line};
+use simulator::Player;
+use trace::{Decision, Candidate};
+use std::time::Instant;
 
 const MAX_HEALTH: u8 = 100;
-const LOW_HEALTH_PER: f32 = 0.25; // represented as percent of max health
-const LOW_HEALTH: u8 = ((MAX_HEALTH as f32) * LOW_HEALTH_PER) as u8;
-const CLOSE_RADIUS: usize = 3; // In tiles
+const MAX_REPLANS: usize = 3; // goals given up in one turn because the first step there is fatal
 
 #[derive(Debug, Clone)]
 struct Location {
@@ -15,12 +40,28 @@ struct Location {
     tile: Tile
 }
 
-fn find_destination(state: &State) -> Option<UVector2> {
+pub struct Bot {
+    params: Params,
+    evaluator: Option<Evaluator>,
+    policy: Option<Box<dyn Policy>>,
+    book: OpeningBook,
+    opening: Line, // our own first moves, to add to the book once the game is over
+    trace: Vec<Decision>,
+    pending: Option<Decision>, // the move we considered, until we learn which move was made
+    mines: MineLedger,
+    opponents: OpponentModel,
+    motion: MotionTracker
+}
+
+fn find_destination(state: &State, params: &Params, ledger: &MineLedger, opponents: &OpponentModel,
+                    influence: &InfluenceMap, territory: &Territory, map: &Map,
+                    decision: &mut Decision) -> Option<UVector2> {
 
     let hero_pos = UVector2::from(&state.hero.pos);
     let bot_life = state.hero.life;
+    // crashed heroes are walls on our map, there is no reaching them to fight
     let mut other_heroes: Vec<&Hero> = state.game.heroes.iter()
-                                                        .filter(|hero| hero.id != state.hero.id)
+                                                        .filter(|hero| hero.id != state.hero.id && !hero.crashed)
                                                         .collect::<Vec<&Hero>>();
     let mut taverns: Vec<Location> = vec!();
     let mut mines: Vec<Location> = vec!();
@@ -41,10 +82,10 @@ fn find_destination(state: &State) -> Option<UVector2> {
         }
     }
 
-    other_heroes.sort_by(|a, b|
-                             hero_pos.distance_from(&UVector2::from(&a.pos))
-                                 .cmp(&hero_pos.distance_from(&UVector2::from(&b.pos)))
-    );
+    let distances = pathing::distance_map(&hero_pos, map);
+    let steps_to = |hero: &Hero| distances[hero.pos.x as usize][hero.pos.y as usize];
+    // whoever can walk up to us first is the one a fight would be with
+    other_heroes.sort_by_key(|hero| steps_to(hero).unwrap_or(usize::max_value()));
 
     let loc_sort = &|a: &Location, b: &Location| -> Ordering {
         hero_pos.distance_from(&a.pos).cmp(&hero_pos.distance_from(&b.pos))
@@ -52,59 +93,196 @@ fn find_destination(state: &State) -> Option<UVector2> {
     taverns.sort_by(loc_sort);
     mines.sort_by(loc_sort);
 
-    if bot_life as u8 <= LOW_HEALTH {
-        println!("Destination: {:#?}", taverns[0]);
-        return Some(taverns[0].pos.clone()) // returns closest tavern
+    let mine_values = mines::evaluate(state, ledger, &distances);
+    let w_best_mine = mine_values.iter().find(|mine| mine.value > 0.0 && !mine.lethal);
+    decision.candidates = mine_values.iter().map(|mine| Candidate {pos: mine.pos.clone(), score: mine.value}).collect();
+
+    // enough life to reach the next mine, pay the goblin and survive a hit on the way
+    let target_life = match w_best_mine {
+        Some(mine) => mine.steps as isize + combat::MINE_DAMAGE + combat::ATTACK_DAMAGE,
+        None => tavern::MAX_LIFE
+    };
+    let w_tavern = tavern::plan(state, map, &distances, target_life);
+
+    if params.exploit_telefrag && !mines.is_empty() {
+        match spawn::telefrag_target(state) {
+            Some(victim) if victim.mine_count > state.hero.mine_count
+                && bot_life <= combat::MINE_DAMAGE
+                && hero_pos.distance_from(&mines[0].pos) == 10 => {
+                return decision.choose(mines[0].pos.clone(), &format!("dying to telefrag enemy {}", victim.id))
+            },
+            _ => {}
+        }
     }
 
-    let enemy = other_heroes[0];
-    let enemy_pos = UVector2::from(&enemy.pos);
-    if hero_pos.distance_from(&enemy_pos) <= CLOSE_RADIUS {
+    let mode = endgame::mode(state);
+    let low_health = ((MAX_HEALTH as f32) * params.low_health_per) as u8;
+    let low_health = match mode {
+        Mode::ProtectLead => low_health.saturating_mul(2), // a death now can hand the game away
+        _ => low_health
+    };
 
-        let enemy_mines = mines.iter().filter(|loc| match loc.tile {
-            Tile::Mine(w_hero_id) => match w_hero_id {
-                Some(hero_id) if hero_id == enemy.id => true,
-                _ => false
+    match w_tavern {
+        Some(ref plan) if plan.steps == 1 && plan.heals > 0 => {
+            return decision.choose(plan.pos.clone(), &format!("buying {} more heals", plan.heals))
+        },
+        Some(ref plan) if bot_life as u8 <= low_health => {
+            return decision.choose(plan.pos.clone(), &format!("healing {} times on low life", plan.heals))
+        },
+        _ => {}
+    }
+
+    if params.camp_when_leading && mode == Mode::ProtectLead {
+        match camping::at_tavern(state, &hero_pos) {
+            Some(tavern) => {
+                if bot_life <= tavern::MAX_LIFE - tavern::HEAL_LIFE && state.hero.gold >= tavern::HEAL_COST {
+                    return decision.choose(tavern, "topping up while camping")
+                }
+                return decision.choose(hero_pos, "camping to protect the lead")
             },
-            _ => false
-        }).collect::<Vec<&Location>>();
-        if enemy.life < bot_life && !enemy_mines.is_empty() {
-            println!("Destination: Enemy {:#?}", enemy.id);
-            return Some(enemy_pos)
+            None => match w_tavern {
+                Some(ref plan) => {
+                    return decision.choose(plan.pos.clone(), "heading off to camp")
+                },
+                None => {}
+            }
         }
-        else if hero_pos.distance_from(&taverns[0].pos) <= CLOSE_RADIUS {
-            println!("Destination: {:#?}, because healh is too low", taverns[0]);
-            return Some(taverns[0].pos.clone())
+    }
+
+    if mode == Mode::Gamble {
+        // go after whoever holds the most mines, if we can catch them and win the fight
+        let w_target = other_heroes.iter()
+                                   .filter(|hero| hero.mine_count > 0)
+                                   .filter(|hero| match steps_to(hero) {
+                                       Some(steps) => steps <= hunting::MAX_CHASE
+                                           && combat::survives_engagement(&elo::judged(state, hero), hero, steps),
+                                       None => false
+                                   })
+                                   .max_by_key(|hero| hero.mine_count);
+        match w_target {
+            Some(target) => {
+                return decision.choose(UVector2::from(&target.pos), &format!("gambling on enemy {} for the lead", target.id))
+            },
+            None => {}
         }
     }
 
-    if hero_pos.distance_from(&mines[0].pos) <= CLOSE_RADIUS {
-        println!("Destination: {:#?}", mines[0]);
-        return Some(mines[0].pos.clone())
+    let danger = influence.danger_at(&hero_pos);
+    match other_heroes.first().and_then(|enemy| steps_to(enemy).map(|steps| (enemy, steps))) {
+        Some((enemy, steps)) if danger > 0.0 => {
+            let enemy_pos = UVector2::from(&enemy.pos);
+            let enemy_mines = mines.iter().filter(|loc| match loc.tile {
+                Tile::Mine(w_hero_id) => match w_hero_id {
+                    Some(hero_id) if hero_id == enemy.id => true,
+                    _ => false
+                },
+                _ => false
+            }).collect::<Vec<&Location>>();
+            if mode != Mode::ProtectLead && !enemy_mines.is_empty()
+                && combat::survives_engagement(&elo::judged(state, enemy), enemy, steps) {
+                return decision.choose(opponents.likely_position(state, enemy.id).unwrap_or(enemy_pos),
+                                       &format!("attacking enemy {}", enemy.id))
+            }
+            else if danger >= params.danger_threshold / elo::caution(state, enemy) {
+                match evade::escape(state, map, influence) {
+                    Some(escape) => {
+                        return decision.choose(escape.target, "fleeing a stronger enemy")
+                    },
+                    None => {}
+                }
+            }
+        },
+        _ => {}
     }
 
-    let closest_enemy_pos = UVector2::from(&other_heroes[0].pos);
-    if other_heroes[0].life < bot_life
-        && hero_pos.distance_from(&closest_enemy_pos) <= CLOSE_RADIUS * 2 {
-        println!("Destination: Enemy {:#?}", other_heroes[0].id);
-        return Some(closest_enemy_pos)
+    match defense::recaptures(state, ledger, map, &mine_values).into_iter().next() {
+        Some(recapture) => {
+            return decision.choose(recapture.pos, &format!("taking back a mine stolen by enemy {}", recapture.thief))
+        },
+        None => {}
+    }
+    match defense::guard(state, map) {
+        Some(guard) => {
+            return decision.choose(guard.pos, &format!("guarding {} mines from enemy {}", guard.mines.len(), guard.threat))
+        },
+        None => {}
+    }
+
+    match crashed::farm(state, &mine_values).into_iter().find(|mine| {
+        influence.danger_at(&mine.pos) < params.danger_threshold
+    }) {
+        Some(mine) => {
+            return decision.choose(mine.pos, "farming a crashed hero")
+        },
+        None => {}
     }
 
-    let unclaimed = mines.iter().filter(|loc| match loc.tile {
-        Tile::Mine(w_hero_id) => w_hero_id.is_none(),
-        _ => false
-    }).collect::<Vec<&Location>>();
+    match w_best_mine {
+        Some(mine) if hero_pos.distance_from(&mine.pos) <= params.close_radius * 10 => {
+            return decision.choose(mine.pos.clone(), "grabbing a close mine")
+        },
+        _ => {}
+    }
 
-    if !unclaimed.is_empty() {
-        println!("Destination: {:#?}", unclaimed[0]);
-        return Some(unclaimed[0].pos.clone())
+    match other_heroes.first().and_then(|enemy| steps_to(enemy).map(|steps| (enemy, steps))) {
+        Some((enemy, steps)) if mode != Mode::ProtectLead && steps <= params.close_radius * 2
+            && combat::survives_engagement(&elo::judged(state, enemy), enemy, steps) => {
+            return decision.choose(opponents.likely_position(state, enemy.id).unwrap_or(UVector2::from(&enemy.pos)),
+                                   &format!("attacking close enemy {}", enemy.id))
+        },
+        _ => {}
     }
-    else {
-        println!("Destination: {:#?}", mines[0]);
-        return Some(mines[0].pos.clone())
+
+    let mut candidates = mine_values.iter()
+                                    .filter(|mine| mine.value > 0.0
+                                        && influence.danger_at(&mine.pos) < params.danger_threshold
+                                        && !lost_race(territory, mine, state.hero.id))
+                                    .cloned()
+                                    .collect::<Vec<mines::MineValue>>();
+    // mines among other mines and taverns are worth more than lone ones
+    for mine in candidates.iter_mut() {
+        mine.value *= 1.0 + influence.opportunity_at(&mine.pos) * params.opportunity_bonus;
+        if crashed::is_farmable(state, mine) {
+            mine.value *= 1.0 + params.farm_bonus;
+        }
     }
+    candidates.sort_by(|a, b| b.value.partial_cmp(&a.value).unwrap_or(Ordering::Equal));
+    decision.candidates = candidates.iter().map(|mine| Candidate {pos: mine.pos.clone(), score: mine.value}).collect();
 
-    None
+    // a kill hands over every mine the victim holds, so weigh it against our best mine
+    if mode != Mode::ProtectLead {
+        let best_mine_value = candidates.first().map_or(0.0, |mine| mine.value);
+        match hunting::rank(state, map).into_iter().next() {
+            Some(bounty) if bounty.gold > best_mine_value => {
+                return decision.choose(opponents.likely_position(state, bounty.hero_id).unwrap_or(bounty.pos),
+                                       &format!("hunting enemy {} for {} mines", bounty.hero_id, bounty.mines))
+            },
+            _ => {}
+        }
+    }
+    match route::plan(state, map, &distances, &candidates) {
+        Some(route) => {
+            decision.choose(route.stops[0].pos.clone(),
+                            &format!("first of {} stops over {} turns", route.stops.len(), route.turns))
+        },
+        None => match w_tavern {
+            Some(ref plan) if bot_life < MAX_HEALTH as isize => {
+                decision.choose(plan.pos.clone(), "healing, no mine is worth the trip")
+            },
+            _ => {
+                decision.reason = "nothing worth doing".to_string();
+                None
+            }
+        }
+    }
+}
+
+/// Whether another hero beats us to `mine` and would take it before we arrive
+fn lost_race(territory: &Territory, mine: &mines::MineValue, hero_id: HeroId) -> bool {
+    match territory.owner_at(&mine.pos) {
+        Some(owner) => owner != hero_id && mine.owner != Some(owner),
+        None => false
+    }
 }
 
 fn get_direction(pos1: &UVector2, pos2: &UVector2) -> Dir {
@@ -125,23 +303,263 @@ fn get_direction(pos1: &UVector2, pos2: &UVector2) -> Dir {
     };
 }
 
-pub fn step(state: &State) -> Dir {
-    let cur_pos = UVector2{x: state.hero.pos.x as usize, y: state.hero.pos.y as usize};
-    let w_destination = find_destination(state);
-    let dir = match w_destination {
-        Some(dest) => {
-            //println!("Destination: {:#?}", dest);
-            let w_path = pathing::gen_path(&cur_pos, &dest, &Map::from(&state.game.board));
+/// Moves onto any free neighboring tile that is not blocked in `map`
+fn step_aside(cur_pos: &UVector2, map: &Map) -> Dir {
+    let i_pos = IVector2::from(cur_pos);
+    let neighbors = [IVector2 {x: i_pos.x - 1, y: i_pos.y}, IVector2 {x: i_pos.x + 1, y: i_pos.y},
+                     IVector2 {x: i_pos.x, y: i_pos.y - 1}, IVector2 {x: i_pos.x, y: i_pos.y + 1}];
+    for neighbor in neighbors.iter() {
+        if neighbor.x < 0 || neighbor.y < 0
+            || neighbor.x >= map.size as isize || neighbor.y >= map.size as isize {
+            continue;
+        }
+        let cell = &map.grid[neighbor.x as usize][neighbor.y as usize];
+        if cell.tile == Tile::Free && !cell.blocked {
+            return get_direction(cur_pos, &cell.pos)
+        }
+    }
+    Dir::Stay
+}
+
+/// First move on the way to `w_destination`, noting the length of the way in `decision`
+fn first_step(cur_pos: &UVector2, w_destination: &Option<UVector2>, map: &Map, decision: &mut Decision) -> Dir {
+    match *w_destination {
+        Some(ref dest) if *dest == *cur_pos => {
+            decision.path_length = Some(0);
+            Dir::Stay
+        },
+        Some(ref dest) => {
+            let w_path = pathing::gen_path(cur_pos, dest, map);
             match w_path {
-                Some(path) => get_direction(&cur_pos, &path.front().unwrap().pos),
+                Some(path) => {
+                    decision.path_length = Some(path.len());
+                    get_direction(cur_pos, &path.front().unwrap().pos)
+                },
                 None => Dir::Stay //Path from bot pos to target is not valid TODO: Find a new target?
             }
         }
-        None => {
-            println!("ERROR: Could not determine a destination.");
-            Dir::Stay
+        None => Dir::Stay
+    }
+}
+
+impl Bot {
+    pub fn new() -> Bot {
+        Bot::with_params(Params::new())
+    }
+
+    pub fn with_params(params: Params) -> Bot {
+        Bot {
+            params: params,
+            evaluator: None,
+            policy: None,
+            book: OpeningBook::new(),
+            opening: Line::new(),
+            trace: vec!(),
+            pending: None,
+            mines: MineLedger::new(),
+            opponents: OpponentModel::new(),
+            motion: MotionTracker::new()
         }
-    };
+    }
 
-    dir
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// Moves by the best scoring action of `evaluator` instead of picking goals by hand
+    pub fn set_evaluator(&mut self, evaluator: Evaluator) {
+        self.evaluator = Some(evaluator);
+    }
+
+    pub fn evaluator(&self) -> Option<&Evaluator> {
+        self.evaluator.as_ref()
+    }
+
+    /// Hands every move after the opening to a policy trained outside the bot, such as an `env::LinearPolicy`
+    pub fn set_policy(&mut self, policy: Box<dyn Policy>) {
+        self.policy = Some(policy);
+    }
+
+    pub fn set_book(&mut self, book: OpeningBook) {
+        self.book = book;
+    }
+
+    pub fn book(&self) -> &OpeningBook {
+        &self.book
+    }
+
+    /// Adds our opening to the book if it beats the known one. Like searched lines, it is
+    /// scored by the gold projected once the book moves are over.
+    pub fn finish(&mut self, state: &State) -> bool {
+        let mut line = self.opening.clone();
+        if opening::own_move(state) <= opening::BOOK_TURNS {
+            line.score = opening::projected_gold(state, state.hero.id);
+        }
+        self.book.record(state, line)
+    }
+
+    /// Why we made each move so far, oldest first
+    pub fn trace(&self) -> &[Decision] {
+        &self.trace
+    }
+
+    pub fn last_decision(&self) -> Option<&Decision> {
+        self.trace.last()
+    }
+
+    pub fn step(&mut self, state: &State) -> Dir {
+        let dir = self.consider(state);
+        self.commit(state, &dir);
+        dir
+    }
+
+    /// Works out our move without playing it: nothing reaches the opening or the trace
+    /// until `commit` says which move was made
+    pub fn consider(&mut self, state: &State) -> Dir {
+        let start = Instant::now();
+        let mut decision = Decision::new(state);
+        self.mines.observe(state);
+        self.opponents.observe(state);
+        self.motion.observe(state);
+
+        let dir = match self.book.next_move(state) {
+            Some(dir) => {
+                decision.reason = "playing the opening book".to_string();
+                safety::filter_noted(state, &Map::from(&state.game.board), dir, self.params.exploit_telefrag, &mut decision)
+            },
+            None => match self.policy {
+                Some(ref mut policy) => {
+                    decision.reason = "move of the trained policy".to_string();
+                    let mut map = Map::from(&state.game.board);
+                    map.block_crashed(&state.game.heroes);
+                    let dir = policy.act(&Observation::encode(state));
+                    safety::filter_noted(state, &map, dir, self.params.exploit_telefrag, &mut decision)
+                },
+                None => self.decide(state, &mut decision)
+            }
+        };
+        decision.dir = dir.clone();
+        decision.set_time(start.elapsed());
+        self.pending = Some(decision);
+        dir
+    }
+
+    /// Records `dir` as the move made on `state`, whether or not it is the one we considered
+    pub fn commit(&mut self, state: &State, dir: &Dir) {
+        if opening::own_move(state) < opening::BOOK_TURNS {
+            self.opening.positions.push(UVector2::from(&state.hero.pos));
+            self.opening.moves.push(dir.clone());
+        } else if opening::own_move(state) == opening::BOOK_TURNS {
+            self.opening.score = opening::projected_gold(state, state.hero.id);
+        }
+        let mut decision = self.pending.take().unwrap_or(Decision::new(state));
+        if decision.dir != *dir {
+            decision.reason = format!("{}, overruled in favour of {}", decision.reason, dir);
+            decision.dir = dir.clone();
+        }
+        self.trace.push(decision);
+    }
+
+    fn decide(&mut self, state: &State, decision: &mut Decision) -> Dir {
+
+        let cur_pos = UVector2{x: state.hero.pos.x as usize, y: state.hero.pos.y as usize};
+        let mut map = Map::from(&state.game.board);
+        map.block_crashed(&state.game.heroes);
+        let unsafe_spawns = spawn::unsafe_spawns(state);
+        for spawn_pos in &unsafe_spawns {
+            map.block(spawn_pos);
+        }
+        for goal in &self.motion.banned() {
+            map.block(goal);
+        }
+        // walking up to a camper that outheals us is walking into its sword
+        let camps = camping::detect(state, &self.opponents);
+        for tavern in &camping::dangerous_taverns(state, &camps) {
+            map.block(tavern);
+        }
+        let influence = InfluenceMap::build(state, &map);
+        influence.apply_costs(&mut map);
+        let territory = Territory::analyze(state, &map);
+
+        let dir = match self.evaluator {
+            Some(ref evaluator) => {
+                decision.reason = "best move of the evaluator".to_string();
+                let mut dir = evaluator.best_avoiding(state, &map, &influence, &self.motion.banned());
+                // where the move leaves us stands in for a goal, so stalls and flip-flops still show
+                let goal = safety::simulate(state, &map, &dir, self.params.exploit_telefrag).pos;
+                self.motion.record_goal(Some(goal.clone()));
+                match self.motion.check() {
+                    Some(stuck) if goal != cur_pos => {
+                        self.motion.ban(&goal, state.game.turn);
+                        map.block(&goal);
+                        dir = evaluator.best_avoiding(state, &map, &influence, &self.motion.banned());
+                        decision.reason = format!("{}, after getting stuck {:?} at ({}, {})",
+                                                  decision.reason, stuck, goal.x, goal.y);
+                    },
+                    _ => {}
+                }
+                dir
+            },
+            None => self.plan(state, &mut map, &influence, &territory, decision)
+        };
+
+        match safety::filter_noted(state, &map, dir, self.params.exploit_telefrag, decision) {
+            Dir::Stay if unsafe_spawns.contains(&cur_pos) => step_aside(&cur_pos, &map),
+            safe => safe
+        }
+    }
+
+    /// The first step toward the goal `find_destination` picks, picking again without
+    /// a goal we keep failing to reach
+    fn plan(&mut self, state: &State, map: &mut Map, influence: &InfluenceMap, territory: &Territory,
+            decision: &mut Decision) -> Dir {
+        let cur_pos = UVector2::from(&state.hero.pos);
+        let mut w_destination = find_destination(state, &self.params, &self.mines, &self.opponents,
+                                                 influence, territory, map, decision);
+        self.motion.record_goal(w_destination.clone());
+        match (self.motion.check(), w_destination.clone()) {
+            (Some(stuck), Some(goal)) => {
+                // drop the goal we keep failing to reach and decide again without it
+                self.motion.ban(&goal, state.game.turn);
+                map.block(&goal);
+                // start the second pick with a clean slate, so nothing of the first one shows
+                decision.goal = None;
+                decision.path_length = None;
+                decision.candidates.clear();
+                w_destination = find_destination(state, &self.params, &self.mines, &self.opponents,
+                                                 influence, territory, map, decision);
+                decision.reason = format!("{}, after getting stuck {:?} on the way to ({}, {})",
+                                          decision.reason, stuck, goal.x, goal.y);
+            },
+            _ => {}
+        }
+
+        // a first step the safety filter would veto leaves us standing still, so pick another goal
+        for _ in 0..MAX_REPLANS {
+            let dir = first_step(&cur_pos, &w_destination, map, decision);
+            match w_destination.clone() {
+                Some(goal) if dir != Dir::Stay && !safety::is_safe(state, map, &dir, self.params.exploit_telefrag) => {
+                    let (dx, dy) = dir.offset();
+                    let next = UVector2 {x: (cur_pos.x as isize + dx) as usize, y: (cur_pos.y as isize + dy) as usize};
+                    map.block(&goal);
+                    map.block(&next);
+                    decision.vetoed = Some(dir);
+                    decision.goal = None;
+                    decision.path_length = None;
+                    decision.candidates.clear();
+                    w_destination = find_destination(state, &self.params, &self.mines, &self.opponents,
+                                                     influence, territory, map, decision);
+                    decision.reason = format!("{}, since the way to ({}, {}) was fatal", decision.reason, goal.x, goal.y);
+                },
+                _ => return dir
+            }
+        }
+        first_step(&cur_pos, &w_destination, map, decision)
+    }
+}
+
+impl Player for Bot {
+    fn play(&mut self, state: &State) -> Dir {
+        self.step(state)
+    }
 }
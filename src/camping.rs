@@ -0,0 +1,77 @@
+use vindinium::{State, Tile, HeroId};
+use pathing::UVector2;
+use opponent::{OpponentModel, Behavior};
+use combat;
+
+const MIN_OBSERVED: usize = 6; // positions we need before calling anyone a camper
+const CAMP_SHARE: f32 = 0.75; // share of recent positions spent next to one tavern
+
+#[derive(Debug, Clone)]
+pub struct Camp {
+    pub hero_id: HeroId,
+    pub tavern: UVector2,
+    pub turns: usize // recent turns spent next to the tavern
+}
+
+fn next_to(a: &UVector2, b: &UVector2) -> bool {
+    a.distance_from(b) == 10
+}
+
+pub fn taverns(state: &State) -> Vec<UVector2> {
+    let mut taverns: Vec<UVector2> = vec!();
+    for (x, row) in state.game.board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            if *tile == Tile::Tavern {
+                taverns.push(UVector2 {x: x, y: y});
+            }
+        }
+    }
+    taverns
+}
+
+/// Whether `pos` is right next to a tavern, so one move buys a heal
+pub fn at_tavern(state: &State, pos: &UVector2) -> Option<UVector2> {
+    taverns(state).into_iter().find(|tavern| next_to(tavern, pos))
+}
+
+/// Enemies that keep hanging around one tavern, healing forever and hitting whoever comes in
+pub fn detect(state: &State, opponents: &OpponentModel) -> Vec<Camp> {
+    let taverns = taverns(state);
+    let mut camps: Vec<Camp> = vec!();
+    for enemy in state.game.heroes.iter().filter(|hero| hero.id != state.hero.id && !hero.crashed) {
+        let profile = match opponents.profile(enemy.id) {
+            Some(profile) if profile.positions.len() >= MIN_OBSERVED => profile,
+            _ => continue
+        };
+        let camped = taverns.iter().map(|tavern| {
+            (tavern, profile.positions.iter().filter(|pos| next_to(tavern, pos)).count())
+        }).max_by_key(|&(_, turns)| turns);
+
+        match camped {
+            Some((tavern, turns)) => {
+                let share = turns as f32 / profile.positions.len() as f32;
+                let here = next_to(tavern, &UVector2::from(&enemy.pos));
+                if here && (share >= CAMP_SHARE || profile.behavior() == Behavior::TavernCamper) {
+                    camps.push(Camp {hero_id: enemy.id, tavern: tavern.clone(), turns: turns});
+                }
+            },
+            None => {}
+        }
+    }
+    camps
+}
+
+/// Camped taverns we would lose a fight at, since the camper heals between every blow
+pub fn dangerous_taverns(state: &State, camps: &[Camp]) -> Vec<UVector2> {
+    camps.iter().filter(|camp| {
+        match state.game.heroes.iter().find(|hero| hero.id == camp.hero_id) {
+            Some(camper) => {
+                // the camper strikes first when we walk up, and tops up on its next turn
+                let mut healed = camper.clone();
+                healed.life = ::std::cmp::min(100, camper.life + 50);
+                !combat::duel(&healed, &state.hero).survives(state.hero.id)
+            },
+            None => false
+        }
+    }).map(|camp| camp.tavern.clone()).collect()
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use vindinium::{Hero, HeroId, Pos};
+
+pub const ATTACK_DAMAGE: isize = 20;
+pub const THIRST_DAMAGE: isize = 1;
+pub const MINE_DAMAGE: isize = 20; // paid to the goblin guarding a mine
+const MAX_EXCHANGES: usize = 64; // bails out of fights nobody can finish
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Death {
+    pub hero_id: HeroId,
+    pub killer_id: HeroId,
+    pub exchange: usize
+}
+
+#[derive(Debug, Clone)]
+pub struct Prediction {
+    pub deaths: Vec<Death>,
+    pub life: HashMap<HeroId, isize>,
+    pub mine_count: HashMap<HeroId, isize>,
+    pub exchanges: usize
+}
+
+#[derive(Debug, Clone)]
+struct Fighter {
+    id: HeroId,
+    pos: Pos,
+    life: isize,
+    mine_count: isize,
+    alive: bool
+}
+
+impl Prediction {
+    pub fn survives(&self, hero_id: HeroId) -> bool {
+        !self.deaths.iter().any(|death| death.hero_id == hero_id)
+    }
+
+    /// The only hero left standing, if the fight ends with exactly one
+    pub fn winner(&self) -> Option<HeroId> {
+        let alive = self.life.keys()
+                             .filter(|id| self.survives(**id))
+                             .collect::<Vec<&HeroId>>();
+        match alive.len() {
+            1 => Some(*alive[0]),
+            _ => None
+        }
+    }
+
+    /// Mines the hero walks away with, including those taken from its victims
+    pub fn mines_of(&self, hero_id: HeroId) -> isize {
+        match self.mine_count.get(&hero_id) {
+            Some(count) => *count,
+            None => 0
+        }
+    }
+}
+
+pub fn adjacent(a: &Pos, b: &Pos) -> bool {
+    (a.x - b.x).abs() + (a.y - b.y).abs() == 1
+}
+
+/// Hero ids in the order they act, starting with whoever moves on `turn`.
+/// The server moves heroes in id order, one hero per turn.
+pub fn turn_order(heroes: &[Hero], turn: isize) -> Vec<HeroId> {
+    let mut ids = heroes.iter().map(|hero| hero.id).collect::<Vec<HeroId>>();
+    ids.sort();
+    if ids.is_empty() {
+        return ids
+    }
+    let first = (turn.abs() as usize) % ids.len();
+    let mut order = ids.split_off(first);
+    order.extend(ids);
+    order
+}
+
+/// Whether the hero about to move lands the first blow on an enemy `steps` tiles away,
+/// assuming the enemy walks into the fight as well.
+pub fn strikes_first(steps: usize) -> bool {
+    steps <= 2 || steps % 2 == 0
+}
+
+fn simulate<F>(mut fighters: Vec<Fighter>, order: &[HeroId], engaged: F) -> Prediction
+    where F: Fn(&Fighter, &Fighter) -> bool {
+    let mut deaths: Vec<Death> = vec!();
+    let mut exchanges = 0;
+
+    'fight: while exchanges < MAX_EXCHANGES {
+        let round_start = exchanges;
+        for id in order {
+            let w_attacker = fighters.iter().position(|f| f.id == *id && f.alive);
+            let attacker = match w_attacker {
+                Some(i) => i,
+                None => continue
+            };
+
+            let targets = (0..fighters.len()).filter(|i| {
+                *i != attacker && fighters[*i].alive && engaged(&fighters[attacker], &fighters[*i])
+            }).collect::<Vec<usize>>();
+            if targets.is_empty() {
+                continue;
+            }
+
+            exchanges += 1;
+            for target in targets {
+                fighters[target].life -= ATTACK_DAMAGE;
+                if fighters[target].life <= 0 {
+                    fighters[target].alive = false;
+                    let loot = fighters[target].mine_count;
+                    fighters[target].mine_count = 0;
+                    fighters[attacker].mine_count += loot;
+                    deaths.push(Death {
+                        hero_id: fighters[target].id,
+                        killer_id: fighters[attacker].id,
+                        exchange: exchanges
+                    });
+                }
+            }
+            // thirst can never kill a hero
+            fighters[attacker].life = ::std::cmp::max(1, fighters[attacker].life - THIRST_DAMAGE);
+
+            let standing = fighters.iter().filter(|f| f.alive).collect::<Vec<&Fighter>>();
+            let fighting = standing.iter().any(|a| standing.iter().any(|b| a.id != b.id && engaged(a, b)));
+            if !fighting {
+                break 'fight;
+            }
+        }
+        if exchanges == round_start || deaths.len() + 1 >= fighters.len() {
+            break;
+        }
+    }
+
+    Prediction {
+        deaths: deaths,
+        life: fighters.iter().map(|f| (f.id, if f.alive { f.life } else { 0 })).collect(),
+        mine_count: fighters.iter().map(|f| (f.id, f.mine_count)).collect(),
+        exchanges: exchanges
+    }
+}
+
+fn to_fighters(heroes: &[&Hero]) -> Vec<Fighter> {
+    heroes.iter().map(|hero| Fighter {
+        id: hero.id,
+        pos: hero.pos.clone(),
+        life: hero.life,
+        mine_count: hero.mine_count,
+        alive: true
+    }).collect()
+}
+
+/// Plays out a brawl between `heroes` who hold their positions, acting in `order`.
+/// Every hero hits each adjacent enemy for `ATTACK_DAMAGE` on its turn, then loses
+/// `THIRST_DAMAGE` life. Killers collect the mines of their victims.
+pub fn predict(heroes: &[&Hero], order: &[HeroId]) -> Prediction {
+    simulate(to_fighters(heroes), order, |a, b| adjacent(&a.pos, &b.pos))
+}
+
+/// Plays out a one-on-one fight as if the heroes were already adjacent
+pub fn duel(first: &Hero, second: &Hero) -> Prediction {
+    simulate(to_fighters(&[first, second]), &[first.id, second.id], |_, _| true)
+}
+
+/// Whether `hero` survives a fight with `enemy` that starts `steps` tiles apart,
+/// with `hero` to move next
+pub fn survives_engagement(hero: &Hero, enemy: &Hero, steps: usize) -> bool {
+    let prediction = if strikes_first(steps) {
+        duel(hero, enemy)
+    } else {
+        duel(enemy, hero)
+    };
+    prediction.survives(hero.id)
+}
@@ -0,0 +1,30 @@
+use vindinium::{State, HeroId};
+use mines::MineValue;
+
+const FARM_RADIUS: usize = 10; // In moves, further mines are left to the router
+pub const FARM_BONUS: f32 = 0.5; // extra mine value, since a crashed owner never takes it back
+
+pub fn crashed_heroes(state: &State) -> Vec<HeroId> {
+    state.game.heroes.iter()
+                     .filter(|hero| hero.id != state.hero.id && hero.crashed)
+                     .map(|hero| hero.id)
+                     .collect()
+}
+
+pub fn is_farmable(state: &State, mine: &MineValue) -> bool {
+    match mine.owner {
+        Some(owner) => crashed_heroes(state).contains(&owner),
+        None => false
+    }
+}
+
+/// Mines held by crashed heroes that we can take right away, nearest first.
+/// Nobody defends them and nobody will take them back.
+pub fn farm(state: &State, values: &[MineValue]) -> Vec<MineValue> {
+    let mut farm = values.iter()
+                         .filter(|mine| !mine.lethal && mine.steps <= FARM_RADIUS && is_farmable(state, mine))
+                         .cloned()
+                         .collect::<Vec<MineValue>>();
+    farm.sort_by_key(|mine| mine.steps);
+    farm
+}
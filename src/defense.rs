@@ -0,0 +1,125 @@
+use vindinium::{State, Tile, HeroId};
+use pathing;
+use pathing::{UVector2, Map};
+use combat;
+use mines::{MineLedger, MineValue};
+
+const RECAPTURE_RADIUS: usize = 8; // In moves, stolen mines further away are left to the router
+const CLUSTER_RADIUS: usize = 4; // In tiles, mines this close together are guarded as one
+const MIN_CLUSTER: usize = 3; // fewer mines are not worth standing around for
+const THREAT_STEPS: usize = 4; // In moves, an enemy this close to a cluster is about to raid it
+
+#[derive(Debug, Clone)]
+pub struct Recapture {
+    pub pos: UVector2,
+    pub thief: HeroId,
+    pub steps: usize,
+    pub thief_steps: Option<usize>, // None when the thief cannot get back to the mine
+    pub thief_weak: bool // we win a fight with the thief
+}
+
+#[derive(Debug, Clone)]
+pub struct Guard {
+    pub pos: UVector2,
+    pub mines: Vec<UVector2>,
+    pub threat: HeroId
+}
+
+/// Stolen mines worth taking back right now, nearest first. A mine qualifies when the thief
+/// is too weak to stop us or too far away to defend it.
+pub fn recaptures(state: &State, ledger: &MineLedger, map: &Map, values: &[MineValue]) -> Vec<Recapture> {
+    let mut recaptures: Vec<Recapture> = vec!();
+    for record in ledger.stolen() {
+        let thief_id = match record.lost_to {
+            Some(thief_id) if record.owner == Some(thief_id) => thief_id,
+            _ => continue
+        };
+        let value = match values.iter().find(|value| value.pos == record.pos) {
+            Some(value) if !value.lethal && value.steps <= RECAPTURE_RADIUS => value,
+            _ => continue
+        };
+        let thief = match state.game.heroes.iter().find(|hero| hero.id == thief_id) {
+            Some(thief) => thief,
+            None => continue
+        };
+        let thief_steps = if thief.crashed {
+            None
+        } else {
+            pathing::distance_map(&UVector2::from(&thief.pos), map)[record.pos.x][record.pos.y]
+        };
+        let thief_weak = thief.crashed || combat::duel(&state.hero, thief).survives(state.hero.id);
+        if thief_weak || thief_steps.map_or(true, |steps| steps > value.steps) {
+            recaptures.push(Recapture {
+                pos: record.pos.clone(),
+                thief: thief_id,
+                steps: value.steps,
+                thief_steps: thief_steps,
+                thief_weak: thief_weak
+            });
+        }
+    }
+    recaptures.sort_by_key(|recapture| recapture.steps);
+    recaptures
+}
+
+fn our_mines(state: &State) -> Vec<UVector2> {
+    let mut mines: Vec<UVector2> = vec!();
+    for (x, row) in state.game.board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            if *tile == Tile::Mine(Some(state.hero.id)) {
+                mines.push(UVector2 {x: x, y: y});
+            }
+        }
+    }
+    mines
+}
+
+/// Where to stand to protect our largest cluster of mines from an enemy closing in on it.
+/// Only worth it against enemies we beat and reach first; stronger raiders are evaded instead.
+pub fn guard(state: &State, map: &Map) -> Option<Guard> {
+    let ours = our_mines(state);
+    let cluster = ours.iter().map(|center| {
+        ours.iter().filter(|mine| center.distance_from(mine) <= CLUSTER_RADIUS * 10)
+                   .cloned()
+                   .collect::<Vec<UVector2>>()
+    }).max_by_key(|cluster| cluster.len());
+    let cluster = match cluster {
+        Some(cluster) if cluster.len() >= MIN_CLUSTER => cluster,
+        _ => return None
+    };
+
+    let hero_pos = UVector2::from(&state.hero.pos);
+    let distances = pathing::distance_map(&hero_pos, map);
+    let mut threats = state.game.heroes.iter().filter(|enemy| enemy.id != state.hero.id && !enemy.crashed)
+                                              .filter_map(|enemy| {
+        let reach = pathing::distance_map(&UVector2::from(&enemy.pos), map);
+        cluster.iter().filter_map(|mine| reach[mine.x][mine.y]).min().map(|steps| (enemy, steps))
+    }).filter(|&(_, steps)| steps <= THREAT_STEPS).collect::<Vec<_>>();
+    threats.sort_by_key(|&(_, steps)| steps);
+
+    let (enemy, steps) = match threats.into_iter().next() {
+        Some(threat) => threat,
+        None => return None
+    };
+    if !combat::duel(&state.hero, enemy).survives(state.hero.id) {
+        return None
+    }
+
+    // stand next to the cluster before the raider reaches it, so it has to come through our sword
+    let neighbors = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+    let w_post = cluster.iter().flat_map(|mine| neighbors.iter().map(move |&(dx, dy)| {
+        (mine.x as isize + dx, mine.y as isize + dy)
+    })).filter_map(|(x, y)| {
+        if x < 0 || y < 0 || x >= map.size as isize || y >= map.size as isize {
+            return None
+        }
+        let (x, y) = (x as usize, y as usize);
+        match (&state.game.board.tiles[x][y], distances[x][y]) {
+            (&Tile::Free, Some(ours)) if ours <= steps => Some((UVector2 {x: x, y: y}, ours)),
+            (&Tile::Hero(id), Some(_)) if id == state.hero.id => Some((UVector2 {x: x, y: y}, 0)),
+            _ => None
+        }
+    }).min_by_key(|&(_, ours)| ours);
+
+    w_post.map(|(pos, _)| Guard {pos: pos, mines: cluster, threat: enemy.id})
+}
@@ -0,0 +1,34 @@
+use vindinium::{State, Hero};
+
+const DEFAULT_ELO: isize = 1200; // rating of a fresh arena account
+pub const ELO_SCALE: f32 = 400.0; // rating gap at which the favorite wins ten to one
+const MIN_CAUTION: f32 = 0.5;
+const MAX_CAUTION: f32 = 1.5;
+const HANDICAP_LIFE: f32 = 40.0; // life we discount ourselves at full caution
+
+/// Expected score of a player rated `ours` against one rated `theirs`, between 0 and 1
+pub fn expected_score(ours: isize, theirs: isize) -> f32 {
+    1.0 / (1.0 + 10.0_f32.powf((theirs - ours) as f32 / ELO_SCALE))
+}
+
+/// Rating relative to a fresh account, in units of `ELO_SCALE`, clamped to [-1, 1]
+pub fn skill(elo: Option<isize>) -> f32 {
+    let elo = elo.unwrap_or(DEFAULT_ELO);
+    ((elo - DEFAULT_ELO) as f32 / ELO_SCALE).max(-1.0).min(1.0)
+}
+
+/// How careful to be around `enemy`: above 1 for stronger opponents, below 1 for weaker ones
+pub fn caution(state: &State, enemy: &Hero) -> f32 {
+    let ours = state.hero.elo.unwrap_or(DEFAULT_ELO);
+    let theirs = enemy.elo.unwrap_or(DEFAULT_ELO);
+    (2.0 * (1.0 - expected_score(ours, theirs))).max(MIN_CAUTION).min(MAX_CAUTION)
+}
+
+/// Our hero as we should judge it before fighting `enemy`. Strong players dodge and
+/// heal better than the fight simulation assumes, so we count on less life against them.
+pub fn judged(state: &State, enemy: &Hero) -> Hero {
+    let mut hero = state.hero.clone();
+    let handicap = ((caution(state, enemy) - 1.0) / (MAX_CAUTION - 1.0) * HANDICAP_LIFE) as isize;
+    hero.life = (hero.life - handicap).max(1).min(100);
+    hero
+}
@@ -0,0 +1,88 @@
+use vindinium::{State, HeroId};
+
+const ENDGAME_TURNS: isize = 40; // own moves left before the endgame kicks in
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mode {
+    Normal,
+    ProtectLead,
+    Gamble
+}
+
+#[derive(Debug, Clone)]
+pub struct Projection {
+    pub hero_id: HeroId,
+    pub gold: isize
+}
+
+/// Number of moves `hero_id` still gets before the game ends
+pub fn turns_left(state: &State, hero_id: HeroId) -> isize {
+    let hero_count = state.game.heroes.len() as isize;
+    if hero_count == 0 {
+        return 0
+    }
+    let remaining = state.game.max_turns - state.game.turn;
+    if remaining <= 0 {
+        return 0
+    }
+    // heroes move in id order, so count only the turns that land on this hero
+    let next_slot = state.game.turn % hero_count;
+    let hero_slot = (hero_id - 1).abs() % hero_count;
+    let offset = (hero_slot - next_slot + hero_count) % hero_count;
+    if offset >= remaining {
+        0
+    } else {
+        (remaining - offset - 1) / hero_count + 1
+    }
+}
+
+/// Final gold of every hero if mines stayed with their current owners, richest first
+pub fn project(state: &State) -> Vec<Projection> {
+    let mut projections = state.game.heroes.iter().map(|hero| Projection {
+        hero_id: hero.id,
+        gold: hero.gold + hero.mine_count * turns_left(state, hero.id)
+    }).collect::<Vec<Projection>>();
+    projections.sort_by(|a, b| b.gold.cmp(&a.gold).then(a.hero_id.cmp(&b.hero_id)));
+    projections
+}
+
+/// Our projected final placing, starting at 1
+pub fn rank(state: &State) -> usize {
+    let projections = project(state);
+    match projections.iter().position(|p| p.hero_id == state.hero.id) {
+        Some(i) => i + 1,
+        None => projections.len()
+    }
+}
+
+/// Gold a rival could swing by killing us and keeping our mines for the rest of the game
+fn swing(state: &State) -> isize {
+    2 * state.hero.mine_count * turns_left(state, state.hero.id)
+}
+
+pub fn mode(state: &State) -> Mode {
+    let projections = project(state);
+    if projections.len() < 2 {
+        return Mode::Normal
+    }
+    let ours = match projections.iter().find(|p| p.hero_id == state.hero.id) {
+        Some(p) => p.gold,
+        None => return Mode::Normal
+    };
+    let best_rival = projections.iter()
+                                .filter(|p| p.hero_id != state.hero.id)
+                                .map(|p| p.gold)
+                                .max()
+                                .unwrap_or(0);
+    let endgame = turns_left(state, state.hero.id) <= ENDGAME_TURNS;
+
+    if ours > best_rival {
+        // only a fight can still cost us the game, so stop picking them
+        if endgame || ours - best_rival > swing(state) {
+            return Mode::ProtectLead
+        }
+    } else if endgame {
+        return Mode::Gamble
+    }
+    Mode::Normal
+}
@@ -0,0 +1,424 @@
+extern crate rustc_serialize;
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::rc::Rc;
+use self::rustc_serialize::json;
+use self::rustc_serialize::json::Json;
+use vindinium::{State, Dir, Hero, HeroId};
+use pathing;
+use pathing::{UVector2, Map};
+use opponent::DIRS;
+use influence::InfluenceMap;
+use features;
+use combat;
+use evade;
+use elo;
+use safety;
+use bot::Bot;
+use simulator;
+use simulator::{Simulator, Player, HERO_COUNT};
+
+const BOT_CONFIDENCE: f32 = 0.5; // the all-round bot is never sure it knows best
+const FLEE_CONFIDENCE: f32 = 0.9;
+const ATTACK_RADIUS: usize = 4; // moves to an enemy worth chasing
+const ATTACK_CONFIDENCE: f32 = 0.5; // plus a tenth per mine the enemy holds
+const PRIORITY_THRESHOLD: f32 = 0.8; // confidence that overrides the vote under priority arbitration
+const GATE_RATE: f32 = 0.01; // learning rate of the gate, per turn of a game
+
+/// A move one strategy would like to make, and how sure it is about it, from 0 to 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proposal {
+    pub dir: Dir,
+    pub confidence: f32
+}
+
+/// Anything that can suggest a move. Strategies that have nothing to say this turn return None.
+pub trait Strategy {
+    fn name(&self) -> &str;
+    fn propose(&mut self, state: &State) -> Option<Proposal>;
+    /// Hears which move was made, once every strategy has proposed
+    fn chosen(&mut self, _state: &State, _dir: &Dir) {}
+}
+
+/// Learned weights for how much to trust each strategy in a situation. Each row holds
+/// a weight per feature of `features::extract`, one row per registered strategy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gate {
+    pub weights: Vec<Vec<f32>>
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Arbitration {
+    /// Every strategy votes for its move with its weight times its confidence
+    WeightedVote,
+    /// The first registered strategy at least this confident decides, else a weighted vote
+    Priority(f32),
+    /// A weighted vote with the weights scaled by the gate for the current situation
+    Gated(Gate)
+}
+
+/// How to set up the ensemble that plays live games: `Combat` registered first, then the bot,
+/// which is also the order of the gate's rows
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnsembleConfig {
+    pub arbitration: Arbitration,
+    pub combat_weight: f32,
+    pub bot_weight: f32
+}
+
+struct Registered {
+    strategy: Box<dyn Strategy>,
+    weight: f32
+}
+
+/// Combines the moves of several registered strategies into one
+pub struct Ensemble {
+    arbitration: Arbitration,
+    strategies: Vec<Registered>,
+    proposals: Vec<(String, Proposal)>,
+    proposers: Vec<usize> // which registered strategy made each proposal
+}
+
+/// A strategy that its owner keeps a hold of, to look at between turns
+pub struct Shared<T: Strategy> {
+    name: String,
+    strategy: Rc<RefCell<T>>
+}
+
+/// Specialist that only speaks up around enemies: it runs from the ones that would win
+/// and chases nearby ones that hold mines and would lose
+pub struct Combat;
+
+fn dot(weights: &[f32], inputs: &[f32]) -> f32 {
+    weights.iter().zip(inputs.iter()).map(|(w, x)| w * x).sum()
+}
+
+/// First move on the way from our position to `target`
+fn first_step(state: &State, map: &Map, target: &UVector2) -> Option<Dir> {
+    let pos = UVector2::from(&state.hero.pos);
+    pathing::gen_path(&pos, target, map).and_then(|path| path.front().map(|next| {
+        let (dx, dy) = (next.pos.x as isize - pos.x as isize, next.pos.y as isize - pos.y as isize);
+        DIRS.iter().find(|dir| dir.offset() == (dx, dy)).cloned().unwrap_or(Dir::Stay)
+    }))
+}
+
+fn board_map(state: &State) -> Map {
+    let mut map = Map::from(&state.game.board);
+    map.block_crashed(&state.game.heroes);
+    map
+}
+
+impl Gate {
+    /// A gate that trusts every strategy equally until it has learned otherwise
+    pub fn new(strategies: usize) -> Gate {
+        Gate {weights: vec![vec![0.0; features::FEATURE_COUNT]; strategies]}
+    }
+
+    /// Softmax share of trust for each strategy given the features of the situation
+    pub fn trust(&self, features: &[f32]) -> Vec<f32> {
+        let scores = self.weights.iter().map(|row| dot(row, features)).collect::<Vec<f32>>();
+        let top = scores.iter().cloned().fold(::std::f32::MIN, f32::max);
+        let exps = scores.iter().map(|score| (score - top).exp()).collect::<Vec<f32>>();
+        let total: f32 = exps.iter().sum();
+        exps.iter().map(|e| e / total).collect()
+    }
+
+    /// Shifts trust toward `winner` in situations like `features`, by a cross entropy step
+    pub fn learn(&mut self, features: &[f32], winner: usize, rate: f32) {
+        let trust = self.trust(features);
+        for (i, row) in self.weights.iter_mut().enumerate() {
+            let error = (if i == winner { 1.0 } else { 0.0 }) - trust[i];
+            for (w, x) in row.iter_mut().zip(features.iter()) {
+                *w += rate * error * x;
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::Array(self.weights.iter().map(|row| {
+            Json::Array(row.iter().map(|w| Json::F64(*w as f64)).collect())
+        }).collect())
+    }
+
+    pub fn from_json(value: &Json) -> Option<Gate> {
+        match *value {
+            Json::Array(ref rows) => rows.iter().map(|row| match *row {
+                Json::Array(ref values) => values.iter().map(|v| v.as_f64().map(|w| w as f32)).collect(),
+                _ => None
+            }).collect::<Option<Vec<Vec<f32>>>>().map(|weights| Gate {weights: weights}),
+            _ => None
+        }
+    }
+}
+
+impl Ensemble {
+    pub fn new(arbitration: Arbitration) -> Ensemble {
+        Ensemble {arbitration: arbitration, strategies: vec!(), proposals: vec!(), proposers: vec!()}
+    }
+
+    /// Adds a strategy with its vote weight. Earlier strategies take priority over later ones.
+    pub fn register(&mut self, strategy: Box<dyn Strategy>, weight: f32) {
+        self.strategies.push(Registered {strategy: strategy, weight: weight});
+    }
+
+    pub fn set_arbitration(&mut self, arbitration: Arbitration) {
+        self.arbitration = arbitration;
+    }
+
+    /// What each strategy proposed on the last turn
+    pub fn proposals(&self) -> &[(String, Proposal)] {
+        &self.proposals
+    }
+
+    fn vote(&self, weights: &[f32]) -> Option<Dir> {
+        let mut tally = DIRS.iter().map(|dir| (dir.clone(), 0.0)).collect::<Vec<(Dir, f32)>>();
+        for (i, &(_, ref proposal)) in self.proposals.iter().enumerate() {
+            match tally.iter_mut().find(|&&mut (ref dir, _)| *dir == proposal.dir) {
+                Some(entry) => entry.1 += weights[i] * proposal.confidence,
+                None => {}
+            }
+        }
+        let mut best: Option<(Dir, f32)> = None;
+        for (dir, score) in tally {
+            if score > best.as_ref().map_or(0.0, |&(_, top)| top) {
+                best = Some((dir, score));
+            }
+        }
+        best.map(|(dir, _)| dir)
+    }
+
+    pub fn step(&mut self, state: &State) -> Dir {
+        // every strategy hears about every turn, so those that track the game stay current
+        let mut weights = vec!();
+        self.proposals.clear();
+        self.proposers.clear();
+        for (i, registered) in self.strategies.iter_mut().enumerate() {
+            match registered.strategy.propose(state) {
+                Some(proposal) => {
+                    self.proposals.push((registered.strategy.name().to_string(), proposal));
+                    self.proposers.push(i);
+                    weights.push((i, registered.weight));
+                },
+                None => {}
+            }
+        }
+
+        let map = board_map(state);
+        let dir = match self.arbitration {
+            Arbitration::WeightedVote => {
+                self.vote(&weights.iter().map(|&(_, weight)| weight).collect::<Vec<f32>>())
+            },
+            Arbitration::Priority(threshold) => {
+                match self.proposals.iter().find(|&&(_, ref proposal)| proposal.confidence >= threshold) {
+                    Some(&(_, ref proposal)) => Some(proposal.dir.clone()),
+                    None => self.vote(&weights.iter().map(|&(_, weight)| weight).collect::<Vec<f32>>())
+                }
+            },
+            Arbitration::Gated(ref gate) => {
+                let influence = InfluenceMap::build(state, &map);
+                let trust = gate.trust(&features::extract(state, &map, &influence, &Dir::Stay));
+                self.vote(&weights.iter().map(|&(i, weight)| {
+                    weight * trust.get(i).cloned().unwrap_or(0.0)
+                }).collect::<Vec<f32>>())
+            }
+        };
+        let dir = safety::filter(state, &map, dir.unwrap_or(Dir::Stay), false);
+        for registered in self.strategies.iter_mut() {
+            registered.strategy.chosen(state, &dir);
+        }
+        dir
+    }
+}
+
+impl EnsembleConfig {
+    /// Combat over the bot, combat deciding alone when it is sure
+    pub fn new() -> EnsembleConfig {
+        EnsembleConfig {arbitration: Arbitration::Priority(PRIORITY_THRESHOLD), combat_weight: 1.0, bot_weight: 1.0}
+    }
+
+    pub fn build<T: Strategy + 'static>(&self, bot: T) -> Ensemble {
+        let mut ensemble = Ensemble::new(self.arbitration.clone());
+        ensemble.register(Box::new(Combat), self.combat_weight);
+        ensemble.register(Box::new(bot), self.bot_weight);
+        ensemble
+    }
+
+    pub fn to_json(&self) -> Json {
+        let mut obj: json::Object = json::Object::new();
+        let kind = match self.arbitration {
+            Arbitration::WeightedVote => "vote",
+            Arbitration::Priority(threshold) => {
+                obj.insert("threshold".to_string(), Json::F64(threshold as f64));
+                "priority"
+            },
+            Arbitration::Gated(ref gate) => {
+                obj.insert("gate".to_string(), gate.to_json());
+                "gated"
+            }
+        };
+        obj.insert("arbitration".to_string(), Json::String(kind.to_string()));
+        obj.insert("combat_weight".to_string(), Json::F64(self.combat_weight as f64));
+        obj.insert("bot_weight".to_string(), Json::F64(self.bot_weight as f64));
+        Json::Object(obj)
+    }
+
+    /// Reads `{"arbitration": "vote" | "priority" | "gated", ...}`, with a `threshold` for
+    /// priority, a `gate` for gated, and optional weights for each strategy
+    pub fn from_json(value: &Json) -> Option<EnsembleConfig> {
+        let weight = |name: &str| value.find(name).and_then(|w| w.as_f64()).unwrap_or(1.0) as f32;
+        let arbitration = match value.find("arbitration").and_then(|kind| kind.as_string()) {
+            Some("vote") => Some(Arbitration::WeightedVote),
+            Some("priority") => Some(Arbitration::Priority(value.find("threshold").and_then(|t| t.as_f64())
+                                                                .map_or(PRIORITY_THRESHOLD, |t| t as f32))),
+            Some("gated") => value.find("gate").and_then(Gate::from_json).map(Arbitration::Gated),
+            _ => None
+        };
+        arbitration.map(|arbitration| EnsembleConfig {
+            arbitration: arbitration,
+            combat_weight: weight("combat_weight"),
+            bot_weight: weight("bot_weight")
+        })
+    }
+
+    pub fn load(filename: &str) -> Option<EnsembleConfig> {
+        let mut json_str = String::new();
+        match File::open(&Path::new(filename)).and_then(|mut file| file.read_to_string(&mut json_str)) {
+            Ok(_) => {},
+            Err(_) => return None
+        }
+        match Json::from_str(&json_str) {
+            Ok(value) => EnsembleConfig::from_json(&value),
+            Err(_) => None
+        }
+    }
+
+    pub fn save(&self, filename: &str) -> Result<(), String> {
+        let text = format!("{}\n", self.to_json().pretty());
+        File::create(&Path::new(filename)).and_then(|mut file| file.write_all(text.as_bytes()))
+                                          .map_err(|err| err.to_string())
+    }
+}
+
+/// Plays one game per seed with `config`'s ensemble, gated by `gate`, in a different seat
+/// each game and bots in the others. Then teaches the gate from each turn the strategies
+/// disagreed on: after a win to trust those whose move was made, after a loss the others.
+pub fn learn_gate(config: &EnsembleConfig, gate: &Gate, seeds: &[usize], turns: isize) -> Gate {
+    let mut gate = gate.clone();
+    for (game, seed) in seeds.iter().enumerate() {
+        let seat = (game % HERO_COUNT) as HeroId + 1;
+        let mut sim = Simulator::new(*seed);
+        sim.game.max_turns = turns;
+        let mut ensemble = config.build(Bot::new());
+        ensemble.set_arbitration(Arbitration::Gated(gate.clone()));
+        let mut others = (0..HERO_COUNT).map(|_| Bot::new()).collect::<Vec<Bot>>();
+
+        // the situation, then the strategies that proposed the move made and those that didn't
+        let mut disputes: Vec<(Vec<f32>, Vec<usize>, Vec<usize>)> = vec!();
+        while !sim.finished() {
+            let id = sim.to_move();
+            let state = sim.state_for(id);
+            let dir = if id == seat {
+                let dir = ensemble.step(&state);
+                let (mut agreed, mut disagreed) = (vec!(), vec!());
+                for (&i, &(_, ref proposal)) in ensemble.proposers.iter().zip(ensemble.proposals.iter()) {
+                    if proposal.dir == dir { agreed.push(i) } else { disagreed.push(i) }
+                }
+                if !agreed.is_empty() && !disagreed.is_empty() {
+                    let map = board_map(&state);
+                    let influence = InfluenceMap::build(&state, &map);
+                    disputes.push((features::extract(&state, &map, &influence, &Dir::Stay), agreed, disagreed));
+                }
+                dir
+            } else {
+                others[id as usize - 1].play(&state)
+            };
+            sim.step(&dir);
+        }
+
+        let won = simulator::rank(&sim.game.heroes, seat) == 1;
+        for (features, agreed, disagreed) in disputes {
+            for i in if won { agreed } else { disagreed } {
+                gate.learn(&features, i, GATE_RATE);
+            }
+        }
+    }
+    gate
+}
+
+impl Player for Ensemble {
+    fn play(&mut self, state: &State) -> Dir {
+        self.step(state)
+    }
+}
+
+impl Strategy for Bot {
+    fn name(&self) -> &str {
+        "bot"
+    }
+
+    fn propose(&mut self, state: &State) -> Option<Proposal> {
+        Some(Proposal {dir: self.consider(state), confidence: BOT_CONFIDENCE})
+    }
+
+    fn chosen(&mut self, state: &State, dir: &Dir) {
+        self.commit(state, dir);
+    }
+}
+
+impl<T: Strategy> Shared<T> {
+    pub fn new(strategy: &Rc<RefCell<T>>) -> Shared<T> {
+        Shared {name: strategy.borrow().name().to_string(), strategy: strategy.clone()}
+    }
+}
+
+impl<T: Strategy> Strategy for Shared<T> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn propose(&mut self, state: &State) -> Option<Proposal> {
+        self.strategy.borrow_mut().propose(state)
+    }
+
+    fn chosen(&mut self, state: &State, dir: &Dir) {
+        self.strategy.borrow_mut().chosen(state, dir)
+    }
+}
+
+impl Strategy for Combat {
+    fn name(&self) -> &str {
+        "combat"
+    }
+
+    fn propose(&mut self, state: &State) -> Option<Proposal> {
+        let map = board_map(state);
+        let influence = InfluenceMap::build(state, &map);
+        match evade::escape(state, &map, &influence) {
+            Some(escape) => return first_step(state, &map, &escape.target).map(|dir| {
+                Proposal {dir: dir, confidence: FLEE_CONFIDENCE}
+            }),
+            None => {}
+        }
+
+        let distances = pathing::distance_map(&UVector2::from(&state.hero.pos), &map);
+        let mut prey = state.game.heroes.iter().filter_map(|enemy| {
+            if enemy.id == state.hero.id || enemy.crashed || enemy.mine_count == 0 {
+                return None
+            }
+            match distances[enemy.pos.x as usize][enemy.pos.y as usize] {
+                Some(steps) if steps <= ATTACK_RADIUS
+                    && combat::survives_engagement(&elo::judged(state, enemy), enemy, steps) => Some((enemy, steps)),
+                _ => None
+            }
+        }).collect::<Vec<(&Hero, usize)>>();
+        prey.sort_by_key(|&(enemy, steps)| (steps, -enemy.mine_count));
+        prey.first().and_then(|&(enemy, _)| {
+            first_step(state, &map, &UVector2::from(&enemy.pos)).map(|dir| Proposal {
+                dir: dir,
+                confidence: (ATTACK_CONFIDENCE + 0.1 * enemy.mine_count as f32).min(1.0)
+            })
+        })
+    }
+}
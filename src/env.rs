@@ -0,0 +1,212 @@
+extern crate rustc_serialize;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use self::rustc_serialize::json::Json;
+use vindinium::{State, Tile, Dir, HeroId};
+use bot::Bot;
+use simulator::{Simulator, Player};
+use opponent::DIRS;
+
+pub const GRID: usize = 28; // largest board the server hands out, smaller ones are padded with wood
+pub const CHANNELS: usize = 7; // wood, tavern, free mine, our mine, enemy mine, us, enemy
+pub const HERO_FEATURES: usize = 5; // life, gold, mines, crashed, whether it is us
+pub const MAX_HEROES: usize = 4;
+pub const OBSERVATION_LEN: usize = CHANNELS * GRID * GRID + MAX_HEROES * HERO_FEATURES + 1;
+pub const GOLD_SCALE: f32 = 1000.0; // gold counted as one unit in observations and features
+pub const MINE_SCALE: f32 = 10.0; // mines counted as one unit in observations and features
+const MINE_REWARD: f32 = 5.0; // reward per mine gained, on top of the gold it starts paying
+
+/// Fixed-size tensor view of a game from one hero's side. Tiles come first, channel by
+/// channel in row-major order, then each hero's stats in id order, then game progress.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    pub data: Vec<f32>
+}
+
+/// Picks moves from observations, for policies trained outside the bot
+pub trait Policy {
+    fn act(&mut self, observation: &Observation) -> Dir;
+}
+
+/// Plays any `Policy` wherever a `Player` is expected, such as in the simulator
+pub struct PolicyPlayer<T: Policy> {
+    pub policy: T
+}
+
+/// A policy trained offline that scores each move by a linear function of the observation.
+/// Each row holds a weight per observation value followed by a bias, one row per move of `DIRS`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearPolicy {
+    pub weights: Vec<Vec<f32>>
+}
+
+/// Training environment around the simulator. We control one hero, bots play the rest,
+/// and every `step` runs until it is our turn again.
+pub struct Env {
+    pub hero_id: HeroId,
+    sim: Simulator,
+    opponents: Vec<Bot>,
+    turns: Option<isize>
+}
+
+impl Observation {
+    pub fn encode(state: &State) -> Observation {
+        let mut data = vec![0.0; OBSERVATION_LEN];
+        let size = state.game.board.size;
+        for x in 0..GRID {
+            for y in 0..GRID {
+                let channel = if x >= size || y >= size {
+                    Some(0)
+                } else {
+                    match state.game.board.tiles[x][y] {
+                        Tile::Free => None,
+                        Tile::Wood => Some(0),
+                        Tile::Tavern => Some(1),
+                        Tile::Mine(None) => Some(2),
+                        Tile::Mine(Some(id)) if id == state.hero.id => Some(3),
+                        Tile::Mine(Some(_)) => Some(4),
+                        Tile::Hero(id) if id == state.hero.id => Some(5),
+                        Tile::Hero(_) => Some(6)
+                    }
+                };
+                match channel {
+                    Some(channel) => data[Observation::index(channel, x, y)] = 1.0,
+                    None => {}
+                }
+            }
+        }
+
+        let stats = CHANNELS * GRID * GRID;
+        for hero in state.game.heroes.iter().take(MAX_HEROES) {
+            let i = stats + (hero.id as usize - 1) * HERO_FEATURES;
+            data[i] = hero.life as f32 / 100.0;
+            data[i + 1] = hero.gold as f32 / GOLD_SCALE;
+            data[i + 2] = hero.mine_count as f32 / MINE_SCALE;
+            data[i + 3] = if hero.crashed { 1.0 } else { 0.0 };
+            data[i + 4] = if hero.id == state.hero.id { 1.0 } else { 0.0 };
+        }
+        data[OBSERVATION_LEN - 1] = state.game.turn as f32 / state.game.max_turns.max(1) as f32;
+        Observation {data: data}
+    }
+
+    /// Position of tile (`x`, `y`) of `channel` in `data`
+    pub fn index(channel: usize, x: usize, y: usize) -> usize {
+        (channel * GRID + x) * GRID + y
+    }
+}
+
+impl<T: Policy> Player for PolicyPlayer<T> {
+    fn play(&mut self, state: &State) -> Dir {
+        self.policy.act(&Observation::encode(state))
+    }
+}
+
+impl Policy for LinearPolicy {
+    fn act(&mut self, observation: &Observation) -> Dir {
+        let mut best = (Dir::Stay, ::std::f32::MIN);
+        for (dir, row) in DIRS.iter().zip(self.weights.iter()) {
+            let bias = row.get(observation.data.len()).cloned().unwrap_or(0.0);
+            let score = row.iter().zip(observation.data.iter()).map(|(w, x)| w * x).sum::<f32>() + bias;
+            if score > best.1 {
+                best = (dir.clone(), score);
+            }
+        }
+        best.0
+    }
+}
+
+impl LinearPolicy {
+    pub fn to_json(&self) -> Json {
+        Json::Array(self.weights.iter().map(|row| {
+            Json::Array(row.iter().map(|w| Json::F64(*w as f64)).collect())
+        }).collect())
+    }
+
+    pub fn from_json(value: &Json) -> Option<LinearPolicy> {
+        match *value {
+            Json::Array(ref rows) => rows.iter().map(|row| match *row {
+                Json::Array(ref values) => values.iter().map(|v| v.as_f64().map(|w| w as f32)).collect(),
+                _ => None
+            }).collect::<Option<Vec<Vec<f32>>>>().map(|weights| LinearPolicy {weights: weights}),
+            _ => None
+        }
+    }
+
+    pub fn load(filename: &str) -> Option<LinearPolicy> {
+        let mut json_str = String::new();
+        match File::open(&Path::new(filename)).and_then(|mut file| file.read_to_string(&mut json_str)) {
+            Ok(_) => {},
+            Err(_) => return None
+        }
+        match Json::from_str(&json_str) {
+            Ok(value) => LinearPolicy::from_json(&value),
+            Err(_) => None
+        }
+    }
+
+    pub fn save(&self, filename: &str) -> Result<(), String> {
+        let text = format!("{}\n", self.to_json());
+        File::create(&Path::new(filename)).and_then(|mut file| file.write_all(text.as_bytes()))
+                                          .map_err(|err| err.to_string())
+    }
+}
+
+impl Env {
+    /// An environment where we play `hero_id` against bots running the default parameters
+    pub fn new(hero_id: HeroId) -> Env {
+        let opponents = (0..MAX_HEROES).map(|_| Bot::new()).collect();
+        Env::with_opponents(hero_id, opponents)
+    }
+
+    /// Same as `new`, with `opponents[i]` playing hero `i + 1`. Our own seat's entry is ignored.
+    pub fn with_opponents(hero_id: HeroId, opponents: Vec<Bot>) -> Env {
+        Env {hero_id: hero_id, sim: Simulator::new(0), opponents: opponents, turns: None}
+    }
+
+    /// Shortens every game to `turns` game turns
+    pub fn set_turns(&mut self, turns: isize) {
+        self.turns = Some(turns);
+    }
+
+    pub fn state(&self) -> State {
+        self.sim.state_for(self.hero_id)
+    }
+
+    /// Lets the bots move until it is our turn or the game is over
+    fn advance(&mut self) {
+        while !self.sim.finished() && self.sim.to_move() != self.hero_id {
+            let id = self.sim.to_move();
+            let state = self.sim.state_for(id);
+            let dir = self.opponents[id as usize - 1].play(&state);
+            self.sim.step(&dir);
+        }
+    }
+
+    /// Starts a new game on the map of `seed`, with fresh opponents
+    pub fn reset(&mut self, seed: usize) -> Observation {
+        self.sim = Simulator::new(seed);
+        match self.turns {
+            Some(turns) => self.sim.game.max_turns = turns,
+            None => {}
+        }
+        let params = self.opponents.iter().map(|bot| bot.params().clone()).collect::<Vec<_>>();
+        self.opponents = params.into_iter().map(Bot::with_params).collect();
+        self.advance();
+        Observation::encode(&self.state())
+    }
+
+    /// Plays `dir` for us and the other heroes' moves after it. The reward is the gold we
+    /// earned in the meantime plus a bonus per mine gained, or a penalty per mine lost.
+    pub fn step(&mut self, dir: Dir) -> (Observation, f32, bool) {
+        let before = self.state().hero;
+        if !self.sim.finished() {
+            self.sim.step(&dir);
+            self.advance();
+        }
+        let state = self.state();
+        let reward = (state.hero.gold - before.gold) as f32
+            + (state.hero.mine_count - before.mine_count) as f32 * MINE_REWARD;
+        (Observation::encode(&state), reward, self.sim.finished())
+    }
+}
@@ -0,0 +1,101 @@
+use std::cmp;
+use vindinium::{State, Tile, Hero};
+use pathing;
+use pathing::{UVector2, Map, DistanceMap};
+use influence::InfluenceMap;
+use combat;
+use tavern;
+
+const THREAT_RADIUS: usize = 6; // In moves
+const ESCAPE_HORIZON: usize = 6; // furthest tile we consider running to, in moves
+const DANGER_WEIGHT: f32 = 2.0; // margin in moves we give up to avoid one unit of danger
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EscapeKind {
+    Tavern,
+    Open
+}
+
+#[derive(Debug, Clone)]
+pub struct Escape {
+    pub target: UVector2,
+    pub kind: EscapeKind,
+    pub margin: isize // moves we stay ahead of the closest threat
+}
+
+/// Enemies within `THREAT_RADIUS` moves that would win a fight against us
+pub fn threats<'a>(state: &'a State, distances: &DistanceMap) -> Vec<&'a Hero> {
+    state.game.heroes.iter().filter(|enemy| {
+        if enemy.id == state.hero.id || enemy.crashed {
+            return false
+        }
+        let steps = match distances[enemy.pos.x as usize][enemy.pos.y as usize] {
+            Some(steps) => steps,
+            None => return false
+        };
+        steps <= THREAT_RADIUS && !combat::survives_engagement(&state.hero, enemy, steps)
+    }).collect()
+}
+
+/// Picks where to run from stronger enemies closing in. A tavern we reach first wins
+/// outright, since a heal flips the fight; otherwise we run for the open tile that keeps
+/// us furthest ahead of every threat while avoiding danger.
+pub fn escape(state: &State, map: &Map, influence: &InfluenceMap) -> Option<Escape> {
+    let hero_pos = UVector2::from(&state.hero.pos);
+    let ours = pathing::distance_map(&hero_pos, map);
+    let hunters = threats(state, &ours);
+    if hunters.is_empty() {
+        return None
+    }
+    let reach = hunters.iter()
+                       .map(|hunter| pathing::distance_map(&UVector2::from(&hunter.pos), map))
+                       .collect::<Vec<DistanceMap>>();
+    let margin_at = |x: usize, y: usize| -> Option<isize> {
+        let mine = match ours[x][y] {
+            Some(d) => d as isize,
+            None => return None
+        };
+        let closest = reach.iter().filter_map(|r| r[x][y]).min().unwrap_or(usize::max_value() / 2);
+        Some(closest as isize - mine)
+    };
+
+    let mut best_tavern: Option<Escape> = None;
+    let mut best_open: Option<(f32, Escape)> = None;
+    for (x, row) in state.game.board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            let margin = match margin_at(x, y) {
+                Some(margin) => margin,
+                None => continue
+            };
+            let pos = UVector2 {x: x, y: y};
+            match *tile {
+                Tile::Tavern if margin > 0 && state.hero.gold >= tavern::HEAL_COST => {
+                    let closer = match best_tavern {
+                        Some(ref b) => ours[x][y] < ours[b.target.x][b.target.y],
+                        None => true
+                    };
+                    if closer {
+                        best_tavern = Some(Escape {target: pos, kind: EscapeKind::Tavern, margin: margin});
+                    }
+                },
+                Tile::Free if ours[x][y].map_or(false, |d| d > 0 && d <= ESCAPE_HORIZON) => {
+                    let score = cmp::min(margin, ESCAPE_HORIZON as isize) as f32
+                        - influence.danger_at(&pos) * DANGER_WEIGHT;
+                    let better = match best_open {
+                        Some((best_score, _)) => score > best_score,
+                        None => true
+                    };
+                    if better {
+                        best_open = Some((score, Escape {target: pos, kind: EscapeKind::Open, margin: margin}));
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+
+    match best_tavern {
+        Some(escape) => Some(escape),
+        None => best_open.map(|(_, escape)| escape)
+    }
+}
@@ -0,0 +1,142 @@
+extern crate rustc_serialize;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use self::rustc_serialize::json;
+use self::rustc_serialize::json::Json;
+use vindinium::{State, Dir};
+use pathing::{Map, UVector2};
+use influence::InfluenceMap;
+use features;
+use safety;
+
+/// Scores a feature vector from `features::extract`, higher is better
+#[derive(Debug, Clone, PartialEq)]
+pub enum Evaluator {
+    Linear(Vec<f32>),
+    /// One tanh hidden layer. Each hidden row holds a weight per feature followed by a bias,
+    /// and `output` a weight per hidden unit followed by a bias.
+    Mlp { hidden: Vec<Vec<f32>>, output: Vec<f32> }
+}
+
+fn dot(weights: &[f32], inputs: &[f32]) -> f32 {
+    weights.iter().zip(inputs.iter()).map(|(w, x)| w * x).sum()
+}
+
+fn numbers(value: &Json) -> Option<Vec<f32>> {
+    match *value {
+        Json::Array(ref values) => values.iter().map(|v| v.as_f64().map(|n| n as f32)).collect(),
+        _ => None
+    }
+}
+
+fn to_json(values: &[f32]) -> Json {
+    Json::Array(values.iter().map(|v| Json::F64(*v as f64)).collect())
+}
+
+impl Evaluator {
+    /// A linear evaluator that likes capturing mines and staying alive, and nothing else
+    pub fn new() -> Evaluator {
+        let mut weights = vec![0.0; features::FEATURE_COUNT];
+        for (i, name) in features::NAMES.iter().enumerate() {
+            weights[i] = match *name {
+                "captures" => 1.0,
+                "mine_steps" => -1.0,
+                "danger" => -0.5,
+                "fatal" => -10.0,
+                _ => 0.0
+            };
+        }
+        Evaluator::Linear(weights)
+    }
+
+    pub fn score(&self, features: &[f32]) -> f32 {
+        match *self {
+            Evaluator::Linear(ref weights) => dot(weights, features),
+            Evaluator::Mlp {ref hidden, ref output} => {
+                let activations = hidden.iter().map(|row| {
+                    let bias = row.get(features.len()).cloned().unwrap_or(0.0);
+                    (dot(row, features) + bias).tanh()
+                }).collect::<Vec<f32>>();
+                let bias = output.get(activations.len()).cloned().unwrap_or(0.0);
+                dot(output, &activations) + bias
+            }
+        }
+    }
+
+    /// The move whose outcome scores highest
+    pub fn best(&self, state: &State, map: &Map, influence: &InfluenceMap) -> Dir {
+        self.best_avoiding(state, map, influence, &[])
+    }
+
+    /// The best scoring move that doesn't leave us on one of the `avoid` tiles
+    pub fn best_avoiding(&self, state: &State, map: &Map, influence: &InfluenceMap, avoid: &[UVector2]) -> Dir {
+        let mut best = (Dir::Stay, ::std::f32::MIN);
+        for (dir, features) in features::extract_all(state, map, influence) {
+            if avoid.contains(&safety::simulate(state, map, &dir, false).pos) {
+                continue;
+            }
+            let score = self.score(&features);
+            if score > best.1 {
+                best = (dir, score);
+            }
+        }
+        best.0
+    }
+
+    pub fn to_json(&self) -> Json {
+        let mut obj: json::Object = json::Object::new();
+        match *self {
+            Evaluator::Linear(ref weights) => {
+                obj.insert("kind".to_string(), Json::String("linear".to_string()));
+                obj.insert("weights".to_string(), to_json(weights));
+            },
+            Evaluator::Mlp {ref hidden, ref output} => {
+                obj.insert("kind".to_string(), Json::String("mlp".to_string()));
+                obj.insert("hidden".to_string(), Json::Array(hidden.iter().map(|row| to_json(row)).collect()));
+                obj.insert("output".to_string(), to_json(output));
+            }
+        }
+        Json::Object(obj)
+    }
+
+    /// Reads `{"kind": "linear", "weights": [...]}` or `{"kind": "mlp", "hidden": [[...]], "output": [...]}`
+    pub fn from_json(value: &Json) -> Option<Evaluator> {
+        let obj = match *value {
+            Json::Object(ref obj) => obj,
+            _ => return None
+        };
+        match obj.get("kind").and_then(|kind| kind.as_string()) {
+            Some("linear") => obj.get("weights").and_then(numbers).map(Evaluator::Linear),
+            Some("mlp") => {
+                let hidden = match obj.get("hidden") {
+                    Some(&Json::Array(ref rows)) => rows.iter().map(numbers).collect::<Option<Vec<Vec<f32>>>>(),
+                    _ => None
+                };
+                match (hidden, obj.get("output").and_then(numbers)) {
+                    (Some(hidden), Some(output)) => Some(Evaluator::Mlp {hidden: hidden, output: output}),
+                    _ => None
+                }
+            },
+            _ => None
+        }
+    }
+
+    pub fn load(filename: &str) -> Option<Evaluator> {
+        let mut json_str = String::new();
+        match File::open(&Path::new(filename)).and_then(|mut file| file.read_to_string(&mut json_str)) {
+            Ok(_) => {},
+            Err(_) => return None
+        }
+        match Json::from_str(&json_str) {
+            Ok(value) => Evaluator::from_json(&value),
+            Err(_) => None
+        }
+    }
+
+    pub fn save(&self, filename: &str) -> Result<(), String> {
+        let text = format!("{}\n", self.to_json().pretty());
+        File::create(&Path::new(filename)).and_then(|mut file| file.write_all(text.as_bytes()))
+                                          .map_err(|err| err.to_string())
+    }
+}
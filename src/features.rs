@@ -0,0 +1,82 @@
+use vindinium::{State, Tile, Dir};
+use pathing;
+use pathing::Map;
+use opponent::DIRS;
+use influence::InfluenceMap;
+use safety;
+use env::{GOLD_SCALE, MINE_SCALE};
+
+pub const FEATURE_COUNT: usize = 12;
+/// Name of each feature, in feature vector order
+pub const NAMES: [&'static str; FEATURE_COUNT] = ["bias", "tavern_steps", "mine_steps", "life", "gold",
+                                                  "our_mines", "captures", "enemy_mines", "enemy_steps",
+                                                  "danger", "threats", "fatal"];
+
+/// Steps from `distances` to the nearest tile matching `wanted`, as a share of the board width.
+/// Unreachable targets count as one board width away.
+fn nearest<F: Fn(&Tile) -> bool>(state: &State, distances: &pathing::DistanceMap, wanted: F) -> f32 {
+    let size = state.game.board.size;
+    let mut best = size;
+    for (x, row) in state.game.board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            if wanted(tile) {
+                match distances[x][y] {
+                    Some(steps) if steps < best => best = steps,
+                    _ => {}
+                }
+            }
+        }
+    }
+    best as f32 / size as f32
+}
+
+/// Describes where moving in `dir` leaves us, as numbers for an evaluator to weigh.
+/// Each entry is scaled to roughly [0, 1]; see `NAMES` for what they mean.
+pub fn extract(state: &State, map: &Map, influence: &InfluenceMap, dir: &Dir) -> Vec<f32> {
+    let outcome = safety::simulate(state, map, dir, false);
+    let distances = pathing::distance_map(&outcome.pos, map);
+    let hero_id = state.hero.id;
+
+    // walking into a mine we don't own leaves us in place but takes the mine
+    let (dx, dy) = dir.offset();
+    let (x, y) = (state.hero.pos.x + dx, state.hero.pos.y + dy);
+    let size = state.game.board.size as isize;
+    let captures = *dir != Dir::Stay && !outcome.fatal && x >= 0 && y >= 0 && x < size && y < size
+        && match state.game.board.tiles[x as usize][y as usize] {
+            Tile::Mine(owner) => owner != Some(hero_id),
+            _ => false
+        };
+
+    let enemy_mines = state.game.heroes.iter()
+                                       .filter(|hero| hero.id != hero_id)
+                                       .map(|hero| hero.mine_count)
+                                       .max()
+                                       .unwrap_or(0);
+    let enemy_steps = nearest(state, &distances, |tile| match *tile {
+        Tile::Hero(id) => id != hero_id,
+        _ => false
+    });
+
+    vec!(
+        1.0,
+        nearest(state, &distances, |tile| *tile == Tile::Tavern),
+        nearest(state, &distances, |tile| match *tile {
+            Tile::Mine(owner) => owner != Some(hero_id),
+            _ => false
+        }),
+        outcome.life as f32 / 100.0,
+        state.hero.gold as f32 / GOLD_SCALE,
+        state.hero.mine_count as f32 / MINE_SCALE,
+        if captures { 1.0 } else { 0.0 },
+        enemy_mines as f32 / MINE_SCALE,
+        enemy_steps,
+        influence.danger_at(&outcome.pos),
+        outcome.threats as f32,
+        if outcome.fatal { 1.0 } else { 0.0 }
+    )
+}
+
+/// Feature vectors for every move we could make, in the order of `opponent::DIRS`
+pub fn extract_all(state: &State, map: &Map, influence: &InfluenceMap) -> Vec<(Dir, Vec<f32>)> {
+    DIRS.iter().map(|dir| (dir.clone(), extract(state, map, influence, dir))).collect()
+}
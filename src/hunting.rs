@@ -0,0 +1,89 @@
+use vindinium::{State, Tile, Hero, HeroId};
+use pathing;
+use pathing::{UVector2, Map, DistanceMap};
+use combat;
+use endgame;
+use tavern;
+use elo;
+use mines;
+
+pub const MAX_CHASE: usize = 12; // In moves, prey further away is gone before we arrive
+
+#[derive(Debug, Clone)]
+pub struct Bounty {
+    pub hero_id: HeroId,
+    pub pos: UVector2,
+    pub steps: usize,
+    pub exchanges: usize,
+    pub mines: isize,
+    pub rate: f32, // mines gained per turn invested
+    pub gold: f32 // expected net gold for the rest of the game
+}
+
+/// Whether `prey` can get to a tavern and heal before we cut it off
+fn escapes_to_tavern(prey: &Hero, ours: &DistanceMap, theirs: &DistanceMap, state: &State) -> bool {
+    if prey.gold < tavern::HEAL_COST {
+        return false
+    }
+    for (x, row) in state.game.board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            if *tile != Tile::Tavern {
+                continue;
+            }
+            match (theirs[x][y], ours[x][y]) {
+                (Some(t), Some(o)) if t < o => return true,
+                (Some(_), None) => return true,
+                _ => {}
+            }
+        }
+    }
+    false
+}
+
+/// Enemies worth hunting for their mines, most profitable first. An enemy only makes the
+/// list if we win the fight and it cannot reach a tavern to heal before we catch it.
+pub fn rank(state: &State, map: &Map) -> Vec<Bounty> {
+    let hero_pos = UVector2::from(&state.hero.pos);
+    let ours = pathing::distance_map(&hero_pos, map);
+    let turns = endgame::turns_left(state, state.hero.id) as f32;
+    let mut bounties: Vec<Bounty> = vec!();
+
+    for prey in state.game.heroes.iter().filter(|hero| hero.id != state.hero.id && hero.mine_count > 0) {
+        let pos = UVector2::from(&prey.pos);
+        let steps = match ours[pos.x][pos.y] {
+            Some(steps) if steps <= MAX_CHASE => steps,
+            _ => continue
+        };
+        let mut hunter = elo::judged(state, prey);
+        hunter.life = tavern::life_after_walk(hunter.life, if steps > 1 { steps - 2 } else { 0 });
+        let prediction = if combat::strikes_first(steps) {
+            combat::duel(&hunter, prey)
+        } else {
+            combat::duel(prey, &hunter)
+        };
+        if !prediction.survives(state.hero.id) {
+            continue;
+        }
+        let theirs = pathing::distance_map(&pos, map);
+        if steps > 2 && escapes_to_tavern(prey, &ours, &theirs, state) {
+            continue;
+        }
+
+        let invested = (steps + prediction.exchanges) as f32;
+        let life_spent = (hunter.life - prediction.life.get(&state.hero.id).cloned().unwrap_or(0)) as f32;
+        bounties.push(Bounty {
+            hero_id: prey.id,
+            pos: pos,
+            steps: steps,
+            exchanges: prediction.exchanges,
+            mines: prey.mine_count,
+            rate: prey.mine_count as f32 / invested.max(1.0),
+            // looted mines can be taken back like any other, so they are held no longer than captured ones
+            gold: prey.mine_count as f32 * mines::expected_hold(1.0 / mines::BASE_HOLD, turns - invested)
+                - life_spent * tavern::LIFE_GOLD
+        });
+    }
+
+    bounties.sort_by(|a, b| b.rate.partial_cmp(&a.rate).unwrap_or(::std::cmp::Ordering::Equal));
+    bounties
+}
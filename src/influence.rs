@@ -0,0 +1,91 @@
+use vindinium::{State, Tile};
+use pathing;
+use pathing::{UVector2, Map};
+use combat;
+
+const DANGER_TURNS: usize = 4; // how far ahead we look for enemies closing in
+const OPPORTUNITY_RADIUS: usize = 10; // In tiles
+const DANGER_COST: f32 = 30.0; // path cost per unit of danger, in tenths of a step
+
+pub type Layer = Vec<Vec<f32>>;
+
+/// Per-tile danger from enemy heroes and opportunity from mines and taverns
+#[derive(Debug, Clone)]
+pub struct InfluenceMap {
+    pub danger: Layer,
+    pub opportunity: Layer
+}
+
+impl InfluenceMap {
+    pub fn build(state: &State, map: &Map) -> InfluenceMap {
+        let size = map.size as usize;
+        let mut danger: Layer = vec![vec![0.0; size]; size];
+        let mut opportunity: Layer = vec![vec![0.0; size]; size];
+
+        for enemy in state.game.heroes.iter().filter(|hero| hero.id != state.hero.id && !hero.crashed) {
+            // an enemy that wins even when we strike first is far worse than one that needs the first blow
+            let threat = if !combat::duel(&state.hero, enemy).survives(state.hero.id) {
+                1.0
+            } else if !combat::duel(enemy, &state.hero).survives(state.hero.id) {
+                0.5
+            } else {
+                0.1
+            };
+            let reach = pathing::distance_map(&UVector2::from(&enemy.pos), map);
+            for x in 0..size {
+                for y in 0..size {
+                    match reach[x][y] {
+                        // being one tile off is enough for it to land a blow
+                        Some(d) if d <= DANGER_TURNS + 1 => {
+                            let turns = if d > 0 { d - 1 } else { 0 };
+                            let decay = (DANGER_TURNS + 1 - turns) as f32 / (DANGER_TURNS + 1) as f32;
+                            danger[x][y] += threat * decay;
+                        },
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let thirst = (100 - state.hero.life) as f32 / 100.0;
+        for (tx, row) in state.game.board.tiles.iter().enumerate() {
+            for (ty, tile) in row.iter().enumerate() {
+                let value = match *tile {
+                    Tile::Mine(owner) if owner != Some(state.hero.id) => 1.0,
+                    Tile::Tavern => thirst,
+                    _ => continue
+                };
+                let reach = pathing::distance_map(&UVector2 {x: tx, y: ty}, map);
+                for x in 0..size {
+                    for y in 0..size {
+                        match reach[x][y] {
+                            Some(d) if d <= OPPORTUNITY_RADIUS => opportunity[x][y] += value / (1 + d) as f32,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        InfluenceMap {danger: danger, opportunity: opportunity}
+    }
+
+    pub fn danger_at(&self, pos: &UVector2) -> f32 {
+        self.danger[pos.x][pos.y]
+    }
+
+    pub fn opportunity_at(&self, pos: &UVector2) -> f32 {
+        self.opportunity[pos.x][pos.y]
+    }
+
+    /// Adds the danger layer to `map` as path costs, so routes bend around enemies
+    pub fn apply_costs(&self, map: &mut Map) {
+        for (x, row) in self.danger.iter().enumerate() {
+            for (y, danger) in row.iter().enumerate() {
+                if *danger > 0.0 {
+                    map.add_cost(&UVector2 {x: x, y: y}, (danger * DANGER_COST) as usize);
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,32 @@
 
 pub mod vindinium;
-pub mod pathing;
\ No newline at end of file
+pub mod pathing;
+pub mod bot;
+pub mod combat;
+pub mod spawn;
+pub mod endgame;
+pub mod mines;
+pub mod tavern;
+pub mod route;
+pub mod opponent;
+pub mod influence;
+pub mod territory;
+pub mod evade;
+pub mod hunting;
+pub mod safety;
+pub mod stalemate;
+pub mod camping;
+pub mod defense;
+pub mod crashed;
+pub mod elo;
+pub mod params;
+pub mod simulator;
+pub mod tuning;
+pub mod env;
+pub mod features;
+pub mod evaluator;
+pub mod training;
+pub mod opening;
+pub mod maps;
+pub mod trace;
+pub mod ensemble;
\ No newline at end of file
@@ -1,16 +1,16 @@
-extern crate hyper;
-extern crate url;
+extern crate vindinium_bot;
 extern crate rustc_serialize;
 use std::string::String;
+use std::rc::Rc;
+use std::cell::RefCell;
 use std::fs::File;
 use std::path::Path;
 use std::io::Read;
 use self::rustc_serialize::json;
 
-use vindinium::*;
-mod pathing;
-mod vindinium;
-mod bot;
+use vindinium_bot::vindinium;
+use vindinium_bot::vindinium::*;
+use vindinium_bot::{bot, params, evaluator, opening, maps, trace, env, ensemble};
 
 
 // Main
@@ -28,6 +28,40 @@ fn main() {
         None => { return (); }
     };
 
+    // per-map data is looked up by the name the map is known under
+    let mut known = maps::KnownMaps::load("maps.json").unwrap_or(maps::KnownMaps::new());
+    let map_name = match settings.mode {
+        Mode::Training(_, ref w_map) => known.recognize(&state.game.board, w_map.as_ref().map(|map| map.as_str())),
+        Mode::Arena => known.recognize(&state.game.board, None)
+    };
+    println!("Playing on map {}", map_name);
+    match known.save("maps.json") {
+        Ok(_) => {},
+        Err(err) => println!("Could not save maps: {}", err)
+    }
+
+    let params = params::Params::load(&format!("params-{}.json", map_name))
+                                .or(params::Params::load("params.json"))
+                                .unwrap_or(params::Params::new());
+    let mut bot = bot::Bot::with_params(params);
+    match evaluator::Evaluator::load("evaluator.json") {
+        Some(evaluator) => bot.set_evaluator(evaluator),
+        None => {}
+    }
+    match env::LinearPolicy::load("policy.json") {
+        Some(policy) => bot.set_policy(Box::new(policy)),
+        None => {}
+    }
+    match opening::OpeningBook::load("openings.json") {
+        Some(book) => bot.set_book(book),
+        None => {}
+    }
+    // the bot is shared with the ensemble, when there is one, so its trace stays at hand
+    let bot = Rc::new(RefCell::new(bot));
+    let mut w_ensemble = ensemble::EnsembleConfig::load("ensemble.json").map(|config| {
+        println!("Playing the ensemble of ensemble.json");
+        config.build(ensemble::Shared::new(&bot))
+    });
     loop {
         if state.game.turn >= state.game.heroes.len() as isize {
             state.clear_pretty_print();
@@ -37,13 +71,32 @@ fn main() {
             println!("Game finished!");
             break;
         }
-        let dir = bot::step(&state);
+        let dir = match w_ensemble {
+            Some(ref mut ensemble) => ensemble.step(&state),
+            None => bot.borrow_mut().step(&state)
+        };
+        match bot.borrow().last_decision() {
+            Some(decision) => println!("{}", decision),
+            None => {}
+        }
         let (url, obj) = step_msg(&settings, &state, dir);
         state = match request(url, obj) {
             Some(s) => s,
             None => { break; },
         }
     }
+    let mut bot = bot.borrow_mut();
+    if bot.finish(&state) {
+        match bot.book().save("openings.json") {
+            Ok(_) => println!("New best opening saved"),
+            Err(err) => println!("Could not save openings: {}", err)
+        }
+    }
+    let trace_file = format!("trace-{}.jsonl", state.game.id);
+    match trace::save(&trace_file, bot.trace()) {
+        Ok(_) => println!("Decisions written to {}", trace_file),
+        Err(err) => println!("Could not save the decision trace: {}", err)
+    }
     println!("see a replay of the game at {}", state.view_url);
 }
 
@@ -0,0 +1,179 @@
+extern crate rustc_serialize;
+use self::rustc_serialize::json;
+use self::rustc_serialize::json::Json;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use vindinium::{Board, Tile, Dir};
+use pathing::UVector2;
+use opponent::DIRS;
+
+const SYMMETRIES: usize = 8; // rotations and reflections of a square board
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+const ARENA: &'static str = "arena-"; // prefix of the maps we only know from the arena
+
+/// One of the eight ways to turn a square board onto itself:
+/// bit 2 transposes, then bit 0 flips rows and bit 1 flips columns
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Symmetry(pub usize);
+
+/// A map we have played on before
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapRecord {
+    pub name: String,
+    pub games: usize
+}
+
+/// Names of the maps we know, by fingerprint
+#[derive(Debug, Clone)]
+pub struct KnownMaps {
+    maps: HashMap<String, MapRecord>
+}
+
+impl Symmetry {
+    pub fn apply(&self, pos: &UVector2, size: usize) -> UVector2 {
+        let (mut x, mut y) = if self.0 & 4 != 0 { (pos.y, pos.x) } else { (pos.x, pos.y) };
+        if self.0 & 1 != 0 {
+            x = size - 1 - x;
+        }
+        if self.0 & 2 != 0 {
+            y = size - 1 - y;
+        }
+        UVector2 {x: x, y: y}
+    }
+
+    /// The position that `apply` turns into `pos`
+    pub fn undo(&self, pos: &UVector2, size: usize) -> UVector2 {
+        let (mut x, mut y) = (pos.x, pos.y);
+        if self.0 & 1 != 0 {
+            x = size - 1 - x;
+        }
+        if self.0 & 2 != 0 {
+            y = size - 1 - y;
+        }
+        if self.0 & 4 != 0 { UVector2 {x: y, y: x} } else { UVector2 {x: x, y: y} }
+    }
+
+    pub fn apply_dir(&self, dir: &Dir) -> Dir {
+        let (dx, dy) = dir.offset();
+        let (mut dx, mut dy) = if self.0 & 4 != 0 { (dy, dx) } else { (dx, dy) };
+        if self.0 & 1 != 0 {
+            dx = -dx;
+        }
+        if self.0 & 2 != 0 {
+            dy = -dy;
+        }
+        DIRS.iter().find(|d| d.offset() == (dx, dy)).cloned().unwrap_or(Dir::Stay)
+    }
+
+    /// The move that `apply_dir` turns into `dir`
+    pub fn undo_dir(&self, dir: &Dir) -> Dir {
+        DIRS.iter().find(|d| self.apply_dir(d) == *dir).cloned().unwrap_or(Dir::Stay)
+    }
+}
+
+fn symbol(tile: &Tile) -> char {
+    match *tile {
+        Tile::Wood => '#',
+        Tile::Tavern => 'T',
+        Tile::Mine(_) => '$',
+        Tile::Free | Tile::Hero(_) => '.'
+    }
+}
+
+/// The layout of `board` seen through `symmetry`, ignoring mine owners and heroes
+fn layout(board: &Board, symmetry: Symmetry) -> String {
+    let mut grid = vec![vec!['.'; board.size]; board.size];
+    for (x, row) in board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            let to = symmetry.apply(&UVector2 {x: x, y: y}, board.size);
+            grid[to.x][to.y] = symbol(tile);
+        }
+    }
+    grid.iter().map(|row| row.iter().cloned().collect::<String>()).collect::<Vec<String>>().join("\n")
+}
+
+/// The symmetry that brings `board` into its canonical orientation, the one whose layout
+/// sorts first. Several may tie on symmetric maps, but they all give the same layout.
+pub fn orientation(board: &Board) -> Symmetry {
+    (0..SYMMETRIES).map(|s| (layout(board, Symmetry(s)), s))
+                   .min()
+                   .map_or(Symmetry(0), |(_, s)| Symmetry(s))
+}
+
+/// Identifies a map whichever way it is turned, regardless of who owns which mine and
+/// where the heroes stand
+pub fn fingerprint(board: &Board) -> String {
+    let canonical = layout(board, orientation(board));
+    let hash = canonical.bytes().fold(FNV_OFFSET, |hash, byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME));
+    format!("{}-{:016x}", board.size, hash)
+}
+
+impl KnownMaps {
+    pub fn new() -> KnownMaps {
+        KnownMaps {maps: HashMap::new()}
+    }
+
+    pub fn len(&self) -> usize {
+        self.maps.len()
+    }
+
+    pub fn identify(&self, board: &Board) -> Option<&MapRecord> {
+        self.maps.get(&fingerprint(board))
+    }
+
+    /// Counts a game on `board` and returns the map's name. `w_name` is the map we asked the
+    /// server for in training, which also renames an arena map once we learn what it is.
+    /// Other new maps are called `arena-N`, numbered on from the highest arena map we know.
+    pub fn recognize(&mut self, board: &Board, w_name: Option<&str>) -> String {
+        let last = self.maps.values().filter_map(|map| {
+            if map.name.starts_with(ARENA) { map.name[ARENA.len()..].parse::<usize>().ok() } else { None }
+        }).max().unwrap_or(0);
+        let next = format!("{}{}", ARENA, last + 1);
+        let record = self.maps.entry(fingerprint(board)).or_insert(MapRecord {name: next, games: 0});
+        match w_name {
+            Some(name) if record.name.starts_with(ARENA) => record.name = name.to_string(),
+            _ => {}
+        }
+        record.games += 1;
+        record.name.clone()
+    }
+
+    pub fn load(filename: &str) -> Option<KnownMaps> {
+        let mut json_str = String::new();
+        match File::open(&Path::new(filename)).and_then(|mut file| file.read_to_string(&mut json_str)) {
+            Ok(_) => {},
+            Err(_) => return None
+        }
+        let obj = match Json::from_str(&json_str) {
+            Ok(Json::Object(obj)) => obj,
+            _ => return None
+        };
+        let mut known = KnownMaps::new();
+        for (key, value) in obj.iter() {
+            match value.find("name").and_then(|name| name.as_string()) {
+                Some(name) => {
+                    let games = value.find("games").and_then(|games| games.as_u64()).unwrap_or(0) as usize;
+                    known.maps.insert(key.clone(), MapRecord {name: name.to_string(), games: games});
+                },
+                None => {}
+            }
+        }
+        Some(known)
+    }
+
+    pub fn save(&self, filename: &str) -> Result<(), String> {
+        let mut obj: json::Object = json::Object::new();
+        for (key, record) in self.maps.iter() {
+            let mut entry: json::Object = json::Object::new();
+            entry.insert("name".to_string(), Json::String(record.name.clone()));
+            entry.insert("games".to_string(), Json::U64(record.games as u64));
+            obj.insert(key.clone(), Json::Object(entry));
+        }
+        let text = format!("{}\n", Json::Object(obj).pretty());
+        File::create(&Path::new(filename)).and_then(|mut file| file.write_all(text.as_bytes()))
+                                          .map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use vindinium::{State, Tile, HeroId};
+use pathing::{UVector2, DistanceMap};
+use combat;
+use endgame;
+use tavern;
+
+pub const BASE_HOLD: f32 = 100.0; // own turns a mine is expected to stay put with nobody around
+const ENEMY_HAZARD: f32 = 0.05; // extra chance per turn of losing a mine for each nearby enemy
+const NEAR_RADIUS: usize = 5; // In tiles
+const DENY_WEIGHT: f32 = 0.5; // share of an enemy's lost income we count as our gain
+
+#[derive(Debug, Clone)]
+pub struct MineRecord {
+    pub pos: UVector2,
+    pub owner: Option<HeroId>,
+    pub flips: usize,
+    pub last_flip: Option<isize>,
+    pub lost_to: Option<HeroId> // enemy holding a mine that used to be ours
+}
+
+/// Remembers every mine's owner between turns so we know how contested each one is
+#[derive(Debug, Clone)]
+pub struct MineLedger {
+    records: HashMap<UVector2, MineRecord>,
+    observed_turns: usize
+}
+
+#[derive(Debug, Clone)]
+pub struct MineValue {
+    pub pos: UVector2,
+    pub owner: Option<HeroId>,
+    pub steps: usize,
+    pub value: f32,
+    pub lethal: bool // capturing it with our current life would kill us
+}
+
+impl MineLedger {
+    pub fn new() -> MineLedger {
+        MineLedger {records: HashMap::new(), observed_turns: 0}
+    }
+
+    pub fn observe(&mut self, state: &State) {
+        self.observed_turns += 1;
+        for (x, row) in state.game.board.tiles.iter().enumerate() {
+            for (y, tile) in row.iter().enumerate() {
+                let owner = match *tile {
+                    Tile::Mine(owner) => owner,
+                    _ => continue
+                };
+                let pos = UVector2 {x: x, y: y};
+                let record = self.records.entry(pos.clone()).or_insert(MineRecord {
+                    pos: pos,
+                    owner: owner,
+                    flips: 0,
+                    last_flip: None,
+                    lost_to: None
+                });
+                if record.owner != owner {
+                    // a mine passed on between enemies is still one of ours gone missing
+                    let was_ours = record.owner == Some(state.hero.id) || record.lost_to.is_some();
+                    record.lost_to = match owner {
+                        Some(thief) if was_ours && thief != state.hero.id => Some(thief),
+                        _ => None
+                    };
+                    record.owner = owner;
+                    record.flips += 1;
+                    record.last_flip = Some(state.game.turn);
+                }
+            }
+        }
+    }
+
+    pub fn get(&self, pos: &UVector2) -> Option<&MineRecord> {
+        self.records.get(pos)
+    }
+
+    /// Mines an enemy took from us and still holds
+    pub fn stolen(&self) -> Vec<&MineRecord> {
+        self.records.values().filter(|record| record.lost_to.is_some()).collect()
+    }
+
+    /// Observed ownership changes per turn we have watched the mine
+    pub fn flip_rate(&self, pos: &UVector2) -> f32 {
+        match self.records.get(pos) {
+            Some(record) if self.observed_turns > 0 => record.flips as f32 / self.observed_turns as f32,
+            _ => 0.0
+        }
+    }
+}
+
+/// Expected gold from a mine we hold for up to `turns` turns, losing it with probability `hazard` each turn
+pub fn expected_hold(hazard: f32, turns: f32) -> f32 {
+    if turns <= 0.0 {
+        return 0.0
+    }
+    (1.0 - (-hazard * turns).exp()) / hazard
+}
+
+/// Expected net gold of capturing each mine we don't own, best first.
+/// Mines we cannot reach are left out.
+pub fn evaluate(state: &State, ledger: &MineLedger, distances: &DistanceMap) -> Vec<MineValue> {
+    let turns = endgame::turns_left(state, state.hero.id) as f32;
+    let mut values: Vec<MineValue> = vec!();
+
+    for (x, row) in state.game.board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            let owner = match *tile {
+                Tile::Mine(owner) if owner != Some(state.hero.id) => owner,
+                _ => continue
+            };
+            let steps = match distances[x][y] {
+                Some(steps) => steps,
+                None => continue
+            };
+            // thirst wears us down on the way and the goblin takes its share on arrival
+            let lethal = state.hero.life - (steps as isize - 1) <= combat::MINE_DAMAGE;
+            let pos = UVector2 {x: x, y: y};
+            let enemies_near = state.game.heroes.iter().filter(|hero| {
+                hero.id != state.hero.id && !hero.crashed
+                    && pos.distance_from(&UVector2::from(&hero.pos)) <= NEAR_RADIUS * 10
+            }).count();
+            let hazard = 1.0 / BASE_HOLD + ledger.flip_rate(&pos) + enemies_near as f32 * ENEMY_HAZARD;
+            let income = expected_hold(hazard, turns - steps as f32);
+            let denied = match owner {
+                Some(_) => income * DENY_WEIGHT,
+                None => 0.0
+            };
+
+            values.push(MineValue {
+                pos: pos,
+                owner: owner,
+                steps: steps,
+                value: income + denied - combat::MINE_DAMAGE as f32 * tavern::LIFE_GOLD,
+                lethal: lethal
+            });
+        }
+    }
+
+    values.sort_by(|a, b| b.value.partial_cmp(&a.value).unwrap_or(::std::cmp::Ordering::Equal));
+    values
+}
@@ -0,0 +1,273 @@
+extern crate rustc_serialize;
+use self::rustc_serialize::json;
+use self::rustc_serialize::json::Json;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use vindinium::{State, Game, Board, Tile, Dir, HeroId};
+use maps;
+use pathing;
+use pathing::{UVector2, Map};
+use opponent::DIRS;
+use bot::Bot;
+use endgame;
+use combat;
+use simulator::{Simulator, Player};
+
+pub const BOOK_TURNS: usize = 20; // own moves played from the book
+const SEARCH_MINES: usize = 3; // nearest mines whose capture orders the search tries
+
+/// An opening for one spawn of one map: the moves to make and where each one is made from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub moves: Vec<Dir>,
+    pub positions: Vec<UVector2>,
+    pub score: f32 // projected final gold when the opening was played
+}
+
+/// Best known opening per map and spawn, kept in the canonical orientation of each map
+#[derive(Debug, Clone)]
+pub struct OpeningBook {
+    lines: HashMap<String, Line>
+}
+
+/// Book key for `spawn` on `board`, in the canonical orientation of the map
+fn line_key(board: &Board, spawn: &UVector2) -> String {
+    let spawn = maps::orientation(board).apply(spawn, board.size);
+    format!("{}@{},{}", maps::fingerprint(board), spawn.x, spawn.y)
+}
+
+/// How many moves our hero has made so far this game
+pub fn own_move(state: &State) -> usize {
+    (state.game.turn / state.game.heroes.len().max(1) as isize) as usize
+}
+
+/// Gold `hero_id` ends the game with if it keeps its mines and nothing else changes,
+/// which is how openings are scored whether we searched or played them
+pub fn projected_gold(state: &State, hero_id: HeroId) -> f32 {
+    let hero = state.game.heroes.iter().find(|hero| hero.id == hero_id).unwrap_or(&state.hero);
+    (hero.gold + hero.mine_count * endgame::turns_left(state, hero_id)) as f32
+}
+
+fn parse_dir(name: &str) -> Option<Dir> {
+    DIRS.iter().find(|dir| dir.to_string() == name).cloned()
+}
+
+fn dir_toward(from: &UVector2, to: &UVector2) -> Dir {
+    let (dx, dy) = (to.x as isize - from.x as isize, to.y as isize - from.y as isize);
+    DIRS.iter().find(|dir| dir.offset() == (dx, dy)).cloned().unwrap_or(Dir::Stay)
+}
+
+impl Line {
+    pub fn new() -> Line {
+        Line {moves: vec!(), positions: vec!(), score: 0.0}
+    }
+
+    fn to_json(&self) -> Json {
+        let mut obj: json::Object = json::Object::new();
+        obj.insert("moves".to_string(), Json::Array(self.moves.iter().map(|dir| Json::String(dir.to_string())).collect()));
+        obj.insert("positions".to_string(), Json::Array(self.positions.iter().map(|pos| {
+            Json::Array(vec!(Json::U64(pos.x as u64), Json::U64(pos.y as u64)))
+        }).collect()));
+        obj.insert("score".to_string(), Json::F64(self.score as f64));
+        Json::Object(obj)
+    }
+
+    fn from_json(value: &Json) -> Option<Line> {
+        let moves = match value.find("moves") {
+            Some(&Json::Array(ref moves)) => moves.iter().map(|dir| dir.as_string().and_then(parse_dir))
+                                                  .collect::<Option<Vec<Dir>>>(),
+            _ => None
+        };
+        let positions = match value.find("positions") {
+            Some(&Json::Array(ref positions)) => positions.iter().map(|pos| match *pos {
+                Json::Array(ref xy) if xy.len() == 2 => match (xy[0].as_u64(), xy[1].as_u64()) {
+                    (Some(x), Some(y)) => Some(UVector2 {x: x as usize, y: y as usize}),
+                    _ => None
+                },
+                _ => None
+            }).collect::<Option<Vec<UVector2>>>(),
+            _ => None
+        };
+        match (moves, positions) {
+            (Some(moves), Some(positions)) => Some(Line {
+                moves: moves,
+                positions: positions,
+                score: value.find("score").and_then(|s| s.as_f64()).unwrap_or(0.0) as f32
+            }),
+            _ => None
+        }
+    }
+}
+
+impl OpeningBook {
+    pub fn new() -> OpeningBook {
+        OpeningBook {lines: HashMap::new()}
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// The opening for the map and spawn of `state`, turned the way the board of `state` is
+    pub fn line(&self, state: &State) -> Option<Line> {
+        let board = &state.game.board;
+        let symmetry = maps::orientation(board);
+        self.lines.get(&line_key(board, &UVector2::from(&state.hero.spawn_pos))).map(|line| Line {
+            moves: line.moves.iter().map(|dir| symmetry.undo_dir(dir)).collect(),
+            positions: line.positions.iter().map(|pos| symmetry.undo(pos, board.size)).collect(),
+            score: line.score
+        })
+    }
+
+    /// The book move for this turn, as long as the game has gone the way the book expects
+    pub fn next_move(&self, state: &State) -> Option<Dir> {
+        let line = match self.line(state) {
+            Some(line) => line,
+            None => return None
+        };
+        let i = own_move(state);
+        match (line.moves.get(i), line.positions.get(i)) {
+            (Some(dir), Some(pos)) if *pos == UVector2::from(&state.hero.pos) => Some(dir.clone()),
+            _ => None
+        }
+    }
+
+    /// Keeps `line`, played on the board of `state`, for its map and spawn if it beats the one we have
+    pub fn record(&mut self, state: &State, line: Line) -> bool {
+        let board = &state.game.board;
+        let symmetry = maps::orientation(board);
+        let line = Line {
+            moves: line.moves.iter().map(|dir| symmetry.apply_dir(dir)).collect(),
+            positions: line.positions.iter().map(|pos| symmetry.apply(pos, board.size)).collect(),
+            score: line.score
+        };
+        let key = line_key(board, &UVector2::from(&state.hero.spawn_pos));
+        let better = match self.lines.get(&key) {
+            Some(known) => line.score > known.score,
+            None => !line.moves.is_empty()
+        };
+        if better {
+            self.lines.insert(key, line);
+        }
+        better
+    }
+
+    pub fn load(filename: &str) -> Option<OpeningBook> {
+        let mut json_str = String::new();
+        match File::open(&Path::new(filename)).and_then(|mut file| file.read_to_string(&mut json_str)) {
+            Ok(_) => {},
+            Err(_) => return None
+        }
+        let obj = match Json::from_str(&json_str) {
+            Ok(Json::Object(obj)) => obj,
+            _ => return None
+        };
+        let mut book = OpeningBook::new();
+        for (key, value) in obj.iter() {
+            match Line::from_json(value) {
+                Some(line) => { book.lines.insert(key.clone(), line); },
+                None => {}
+            }
+        }
+        Some(book)
+    }
+
+    pub fn save(&self, filename: &str) -> Result<(), String> {
+        let mut obj: json::Object = json::Object::new();
+        for (key, line) in self.lines.iter() {
+            obj.insert(key.clone(), line.to_json());
+        }
+        let text = format!("{}\n", Json::Object(obj).pretty());
+        File::create(&Path::new(filename)).and_then(|mut file| file.write_all(text.as_bytes()))
+                                          .map_err(|err| err.to_string())
+    }
+}
+
+/// Walks to `targets` in order, skipping the ones we already own, then lets the bot take over
+struct MineOrder {
+    targets: Vec<UVector2>,
+    fallback: Bot
+}
+
+impl Player for MineOrder {
+    fn play(&mut self, state: &State) -> Dir {
+        let fallback = self.fallback.step(state);
+        self.targets.retain(|mine| state.game.board.tiles[mine.x][mine.y] != Tile::Mine(Some(state.hero.id)));
+        let pos = UVector2::from(&state.hero.pos);
+        let map = Map::from(&state.game.board);
+        match self.targets.first().and_then(|target| pathing::gen_path(&pos, target, &map)) {
+            // too weak to pay the goblin on arrival, leave the healing to the bot
+            Some(ref path) if state.hero.life > combat::MINE_DAMAGE + path.len() as isize => {
+                dir_toward(&pos, &path.front().unwrap().pos)
+            },
+            _ => fallback
+        }
+    }
+}
+
+/// Plays the first `turns` moves of `hero_id` in a copy of `game` with bots on the other seats
+fn play_out<P: Player>(game: &Game, hero_id: HeroId, turns: usize, player: &mut P) -> Line {
+    let mut sim = Simulator::from_game(game.clone());
+    let mut others = (0..game.heroes.len()).map(|_| Bot::new()).collect::<Vec<Bot>>();
+    let mut line = Line::new();
+    while !sim.finished() && line.moves.len() < turns {
+        let id = sim.to_move();
+        let state = sim.state_for(id);
+        let dir = if id == hero_id {
+            line.positions.push(UVector2::from(&state.hero.pos));
+            let dir = player.play(&state);
+            line.moves.push(dir.clone());
+            dir
+        } else {
+            others[id as usize - 1].play(&state)
+        };
+        sim.step(&dir);
+    }
+    line.score = projected_gold(&sim.state_for(hero_id), hero_id);
+    line
+}
+
+fn permutations(items: &[UVector2]) -> Vec<Vec<UVector2>> {
+    if items.len() <= 1 {
+        return vec!(items.to_vec())
+    }
+    let mut all = vec!();
+    for (i, first) in items.iter().enumerate() {
+        let mut rest = items.to_vec();
+        rest.remove(i);
+        for mut tail in permutations(&rest) {
+            tail.insert(0, first.clone());
+            all.push(tail);
+        }
+    }
+    all
+}
+
+/// Searches for the best opening of `hero_id` from the start of `game`: the bot's own play
+/// against every order of grabbing the nearest few mines, scored by projected final gold.
+pub fn search(game: &Game, hero_id: HeroId, turns: usize) -> Line {
+    let hero = &game.heroes[hero_id as usize - 1];
+    let distances = pathing::distance_map(&UVector2::from(&hero.pos), &Map::from(&game.board));
+    let mut mines: Vec<(UVector2, usize)> = vec!();
+    for (x, row) in game.board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            match (tile, distances[x][y]) {
+                (&Tile::Mine(owner), Some(steps)) if owner != Some(hero_id) => mines.push((UVector2 {x: x, y: y}, steps)),
+                _ => {}
+            }
+        }
+    }
+    mines.sort_by_key(|&(_, steps)| steps);
+    let nearest = mines.into_iter().take(SEARCH_MINES).map(|(pos, _)| pos).collect::<Vec<UVector2>>();
+
+    let mut best = play_out(game, hero_id, turns, &mut Bot::new());
+    for order in permutations(&nearest) {
+        let line = play_out(game, hero_id, turns, &mut MineOrder {targets: order, fallback: Bot::new()});
+        if line.score > best.score {
+            best = line;
+        }
+    }
+    best
+}
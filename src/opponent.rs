@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use vindinium::{State, Hero, HeroId, Tile, Dir};
+use pathing;
+use pathing::{UVector2, Map, DistanceMap};
+use elo;
+
+const HISTORY_LEN: usize = 20; // moves remembered per hero
+const NOISE: f32 = 0.1; // share of every policy spread evenly over all moves
+const MEMORY: f32 = 0.95; // how much of the old evidence survives each new move
+const ELO_PRIOR: f32 = 1.0; // log-odds a rating gap of `elo::ELO_SCALE` is worth
+
+pub const DIRS: [Dir; 5] = [Dir::Stay, Dir::North, Dir::South, Dir::East, Dir::West];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Behavior {
+    Miner,
+    Hunter,
+    TavernCamper,
+    Random,
+    Crashed
+}
+
+pub const BEHAVIORS: [Behavior; 5] = [Behavior::Miner, Behavior::Hunter, Behavior::TavernCamper,
+                                      Behavior::Random, Behavior::Crashed];
+
+pub type DirDistribution = Vec<(Dir, f32)>;
+
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub hero_id: HeroId,
+    pub moves: VecDeque<Dir>,
+    pub positions: VecDeque<UVector2>,
+    pub deaths: usize,
+    log_weights: Vec<f32> // one per entry of `BEHAVIORS`
+}
+
+/// Watches every enemy hero from turn to turn and guesses how each one plays
+#[derive(Debug, Clone)]
+pub struct OpponentModel {
+    profiles: HashMap<HeroId, Profile>,
+    last: Option<State>
+}
+
+impl Profile {
+    fn new(hero_id: HeroId, elo: Option<isize>) -> Profile {
+        Profile {
+            hero_id: hero_id,
+            moves: VecDeque::new(),
+            positions: VecDeque::new(),
+            deaths: 0,
+            log_weights: prior(elo)
+        }
+    }
+
+    /// Probability of each behavior, in the order of `BEHAVIORS`
+    pub fn beliefs(&self) -> Vec<(Behavior, f32)> {
+        let max = self.log_weights.iter().cloned().fold(::std::f32::MIN, f32::max);
+        let weights = self.log_weights.iter().map(|w| (w - max).exp()).collect::<Vec<f32>>();
+        let total: f32 = weights.iter().sum();
+        BEHAVIORS.iter().cloned().zip(weights.iter().map(|w| w / total)).collect()
+    }
+
+    pub fn probability(&self, behavior: &Behavior) -> f32 {
+        self.beliefs().iter()
+                      .find(|&&(ref b, _)| b == behavior)
+                      .map_or(0.0, |&(_, p)| p)
+    }
+
+    /// The most likely behavior
+    pub fn behavior(&self) -> Behavior {
+        let mut beliefs = self.beliefs();
+        beliefs.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(::std::cmp::Ordering::Equal));
+        beliefs[0].0.clone()
+    }
+
+    fn record(&mut self, dir: Dir, pos: UVector2) {
+        self.moves.push_back(dir);
+        self.positions.push_back(pos);
+        while self.moves.len() > HISTORY_LEN {
+            self.moves.pop_front();
+        }
+        while self.positions.len() > HISTORY_LEN {
+            self.positions.pop_front();
+        }
+    }
+}
+
+/// Starting log weights for a hero rated `elo`. Highly rated bots play with purpose, and
+/// camping is the arena meta; low rated ones are more often random or half finished.
+fn prior(elo: Option<isize>) -> Vec<f32> {
+    let skill = elo::skill(elo) * ELO_PRIOR;
+    BEHAVIORS.iter().map(|behavior| match *behavior {
+        Behavior::Miner | Behavior::Hunter => skill * 0.5,
+        Behavior::TavernCamper => skill,
+        Behavior::Random => -skill,
+        Behavior::Crashed => 0.0
+    }).collect()
+}
+
+fn neighbor(pos: &UVector2, dir: &Dir, size: usize) -> Option<UVector2> {
+    let (dx, dy) = dir.offset();
+    let x = pos.x as isize + dx;
+    let y = pos.y as isize + dy;
+    if x < 0 || y < 0 || x >= size as isize || y >= size as isize {
+        return None
+    }
+    Some(UVector2 {x: x as usize, y: y as usize})
+}
+
+/// Every move that brings `pos` closer to a cell at distance zero in `distances`
+fn moves_toward(pos: &UVector2, distances: &DistanceMap) -> Vec<Dir> {
+    let here = match distances[pos.x][pos.y] {
+        Some(d) => d,
+        None => return vec!()
+    };
+    DIRS.iter().filter(|dir| match neighbor(pos, dir, distances.len()) {
+        Some(next) => match distances[next.x][next.y] {
+            Some(d) => d < here,
+            None => false
+        },
+        None => false
+    }).cloned().collect()
+}
+
+/// Distances from the closest of `targets`, reachable by walking back to `pos`
+fn nearest_target(pos: &UVector2, targets: &[UVector2], map: &Map) -> Option<DistanceMap> {
+    let from_hero = pathing::distance_map(pos, map);
+    targets.iter()
+           .filter(|t| from_hero[t.x][t.y].is_some())
+           .min_by_key(|t| from_hero[t.x][t.y].unwrap())
+           .map(|t| pathing::distance_map(t, map))
+}
+
+fn spread(dirs: &[Dir]) -> DirDistribution {
+    let noise = NOISE / DIRS.len() as f32;
+    DIRS.iter().map(|dir| {
+        let p = if dirs.is_empty() {
+            1.0 / DIRS.len() as f32
+        } else if dirs.contains(dir) {
+            (1.0 - NOISE) / dirs.len() as f32 + noise
+        } else {
+            noise
+        };
+        (dir.clone(), p)
+    }).collect()
+}
+
+/// How a hero with the given behavior would move in `state`
+fn policy(behavior: &Behavior, hero: &Hero, state: &State, map: &Map) -> DirDistribution {
+    let pos = UVector2::from(&hero.pos);
+    let mut mines: Vec<UVector2> = vec!();
+    let mut taverns: Vec<UVector2> = vec!();
+    for (x, row) in state.game.board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            match *tile {
+                Tile::Mine(owner) if owner != Some(hero.id) => mines.push(UVector2 {x: x, y: y}),
+                Tile::Tavern => taverns.push(UVector2 {x: x, y: y}),
+                _ => {}
+            }
+        }
+    }
+
+    match *behavior {
+        Behavior::Miner => match nearest_target(&pos, &mines, map) {
+            Some(distances) => spread(&moves_toward(&pos, &distances)),
+            None => spread(&[])
+        },
+        Behavior::Hunter => {
+            let others = state.game.heroes.iter()
+                                          .filter(|other| other.id != hero.id)
+                                          .map(|other| UVector2::from(&other.pos))
+                                          .collect::<Vec<UVector2>>();
+            match nearest_target(&pos, &others, map) {
+                Some(distances) => spread(&moves_toward(&pos, &distances)),
+                None => spread(&[])
+            }
+        },
+        Behavior::TavernCamper => match nearest_target(&pos, &taverns, map) {
+            Some(ref distances) if distances[pos.x][pos.y] == Some(1) => {
+                // campers sit by the bar, buying a drink now and then
+                let mut dirs = moves_toward(&pos, distances);
+                dirs.push(Dir::Stay);
+                spread(&dirs)
+            },
+            Some(distances) => spread(&moves_toward(&pos, &distances)),
+            None => spread(&[Dir::Stay])
+        },
+        Behavior::Random => spread(&[]),
+        Behavior::Crashed => spread(&[Dir::Stay])
+    }
+}
+
+/// The move `before` made to become `after`, or `None` if it died and respawned.
+/// Bumping into a mine or tavern leaves a hero in place, so we look for what it touched.
+fn infer_move(before: &Hero, after: &Hero, state: &State) -> Option<Dir> {
+    let dx = after.pos.x - before.pos.x;
+    let dy = after.pos.y - before.pos.y;
+    let moved = DIRS.iter().find(|dir| dir.offset() == (dx, dy)).cloned();
+    match moved {
+        Some(Dir::Stay) => {},
+        other => return other
+    }
+
+    let pos = UVector2::from(&before.pos);
+    let size = state.game.board.size;
+    for dir in DIRS.iter().skip(1) {
+        let next = match neighbor(&pos, dir, size) {
+            Some(next) => next,
+            None => continue
+        };
+        let touched = match state.game.board.tiles[next.x][next.y] {
+            Tile::Mine(Some(owner)) => owner == after.id && after.mine_count > before.mine_count,
+            Tile::Tavern => after.life > before.life,
+            _ => false
+        };
+        if touched {
+            return Some(dir.clone())
+        }
+    }
+    Some(Dir::Stay)
+}
+
+impl OpponentModel {
+    pub fn new() -> OpponentModel {
+        OpponentModel {profiles: HashMap::new(), last: None}
+    }
+
+    pub fn observe(&mut self, state: &State) {
+        for hero in state.game.heroes.iter().filter(|hero| hero.id != state.hero.id) {
+            let w_before = match self.last {
+                Some(ref last) => last.game.heroes.iter().find(|h| h.id == hero.id).cloned(),
+                None => None
+            };
+            let profile = self.profiles.entry(hero.id).or_insert(Profile::new(hero.id, hero.elo));
+            let before = match w_before {
+                Some(before) => before,
+                None => {
+                    profile.positions.push_back(UVector2::from(&hero.pos));
+                    continue;
+                }
+            };
+
+            let dir = match infer_move(&before, hero, state) {
+                Some(dir) => dir,
+                None => {
+                    profile.deaths += 1;
+                    continue;
+                }
+            };
+            let last_state = self.last.as_ref().unwrap();
+            let last_map = Map::from(&last_state.game.board);
+            for (i, behavior) in BEHAVIORS.iter().enumerate() {
+                let likelihood = policy(behavior, &before, last_state, &last_map).iter()
+                                                                             .find(|&&(ref d, _)| *d == dir)
+                                                                             .map_or(NOISE, |&(_, p)| p);
+                profile.log_weights[i] = profile.log_weights[i] * MEMORY + likelihood.ln();
+            }
+            if hero.crashed {
+                let crashed = BEHAVIORS.iter().position(|b| *b == Behavior::Crashed).unwrap();
+                for (i, weight) in profile.log_weights.iter_mut().enumerate() {
+                    *weight = if i == crashed { 0.0 } else { ::std::f32::MIN / 2.0 };
+                }
+            }
+            profile.record(dir, UVector2::from(&hero.pos));
+        }
+        self.last = Some(state.clone());
+    }
+
+    pub fn profile(&self, hero_id: HeroId) -> Option<&Profile> {
+        self.profiles.get(&hero_id)
+    }
+
+    /// Chance of each move `hero_id` makes next, mixing the policy of every behavior by its probability
+    pub fn predict(&self, state: &State, hero_id: HeroId) -> DirDistribution {
+        let hero = match state.game.heroes.iter().find(|hero| hero.id == hero_id) {
+            Some(hero) => hero,
+            None => return spread(&[])
+        };
+        let beliefs = match self.profiles.get(&hero_id) {
+            Some(profile) => profile.beliefs(),
+            None => BEHAVIORS.iter().map(|b| (b.clone(), 1.0 / BEHAVIORS.len() as f32)).collect()
+        };
+
+        let map = Map::from(&state.game.board);
+        let mut prediction = DIRS.iter().map(|dir| (dir.clone(), 0.0)).collect::<DirDistribution>();
+        for &(ref behavior, weight) in &beliefs {
+            for (i, &(_, p)) in policy(behavior, hero, state, &map).iter().enumerate() {
+                prediction[i].1 += weight * p;
+            }
+        }
+        prediction
+    }
+
+    /// The single most likely next move of `hero_id`
+    pub fn likely_move(&self, state: &State, hero_id: HeroId) -> Dir {
+        let prediction = self.predict(state, hero_id);
+        prediction.iter()
+                  .fold((Dir::Stay, -1.0), |best, &(ref dir, p)| if p > best.1 { (dir.clone(), p) } else { best })
+                  .0
+    }
+
+    /// Where `hero_id` most likely stands after its next move
+    pub fn likely_position(&self, state: &State, hero_id: HeroId) -> Option<UVector2> {
+        let hero = match state.game.heroes.iter().find(|hero| hero.id == hero_id) {
+            Some(hero) => hero,
+            None => return None
+        };
+        let pos = UVector2::from(&hero.pos);
+        match neighbor(&pos, &self.likely_move(state, hero_id), state.game.board.size) {
+            Some(next) => match state.game.board.tiles[next.x][next.y] {
+                Tile::Free => Some(next),
+                _ => Some(pos)
+            },
+            None => Some(pos)
+        }
+    }
+}
@@ -0,0 +1,110 @@
+extern crate rustc_serialize;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use self::rustc_serialize::json;
+use self::rustc_serialize::json::Json;
+use crashed;
+
+/// Names of the tunable parameters, in parameter vector order
+pub const NAMES: [&'static str; 7] = ["low_health_per", "close_radius", "danger_threshold",
+                                      "opportunity_bonus", "farm_bonus", "exploit_telefrag", "camp_when_leading"];
+/// Smallest and largest sensible value of each parameter, in parameter vector order
+pub const BOUNDS: [(f32, f32); 7] = [(0.05, 0.8), (1.0, 10.0), (0.05, 2.0), (0.0, 2.0), (0.0, 3.0), (0.0, 1.0),
+                                     (0.0, 1.0)];
+
+/// Strategy knobs of the bot, tuned offline and loaded at startup
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    pub low_health_per: f32, // represented as percent of max health
+    pub close_radius: usize, // In tiles
+    pub danger_threshold: f32, // danger at which we stop walking into a tile
+    pub opportunity_bonus: f32, // extra mine value per unit of opportunity around it
+    pub farm_bonus: f32, // extra mine value of mines held by crashed heroes
+    pub exploit_telefrag: bool, // die on purpose to respawn onto an enemy camping our spawn
+    pub camp_when_leading: bool // sit by a tavern and heal out the clock once the lead is safe
+}
+
+impl Params {
+    pub fn new() -> Params {
+        Params {
+            low_health_per: 0.25,
+            close_radius: 3,
+            danger_threshold: 0.5,
+            opportunity_bonus: 0.25,
+            farm_bonus: crashed::FARM_BONUS,
+            exploit_telefrag: false,
+            camp_when_leading: true
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<f32> {
+        vec!(self.low_health_per, self.close_radius as f32, self.danger_threshold,
+             self.opportunity_bonus, self.farm_bonus, if self.exploit_telefrag { 1.0 } else { 0.0 },
+             if self.camp_when_leading { 1.0 } else { 0.0 })
+    }
+
+    /// Parameters from a vector in `NAMES` order, clamped to `BOUNDS`
+    pub fn from_vec(values: &[f32]) -> Params {
+        let mut params = Params::new().to_vec();
+        for (i, value) in values.iter().enumerate().take(params.len()) {
+            let (low, high) = BOUNDS[i];
+            params[i] = value.max(low).min(high);
+        }
+        Params {
+            low_health_per: params[0],
+            close_radius: params[1].round() as usize,
+            danger_threshold: params[2],
+            opportunity_bonus: params[3],
+            farm_bonus: params[4],
+            exploit_telefrag: params[5] >= 0.5,
+            camp_when_leading: params[6] >= 0.5
+        }
+    }
+
+    pub fn to_json(&self) -> Json {
+        let mut obj: json::Object = json::Object::new();
+        for (name, value) in NAMES.iter().zip(self.to_vec()) {
+            obj.insert(name.to_string(), Json::F64(value as f64));
+        }
+        Json::Object(obj)
+    }
+
+    /// Parameters from an object keyed by `NAMES`. Missing entries keep their default.
+    pub fn from_json(value: &Json) -> Option<Params> {
+        let obj = match *value {
+            Json::Object(ref obj) => obj,
+            _ => return None
+        };
+        let mut values = Params::new().to_vec();
+        for (i, name) in NAMES.iter().enumerate() {
+            let w_value = obj.get(*name).and_then(|value| {
+                value.as_f64().or(value.as_boolean().map(|on| if on { 1.0 } else { 0.0 }))
+            });
+            match w_value {
+                Some(value) => values[i] = value as f32,
+                None => {}
+            }
+        }
+        Some(Params::from_vec(&values))
+    }
+
+    /// Reads parameters saved by `save`
+    pub fn load(filename: &str) -> Option<Params> {
+        let mut json_str = String::new();
+        match File::open(&Path::new(filename)).and_then(|mut file| file.read_to_string(&mut json_str)) {
+            Ok(_) => {},
+            Err(_) => return None
+        }
+        match Json::from_str(&json_str) {
+            Ok(value) => Params::from_json(&value),
+            Err(_) => None
+        }
+    }
+
+    pub fn save(&self, filename: &str) -> Result<(), String> {
+        let text = format!("{}\n", self.to_json().pretty());
+        File::create(&Path::new(filename)).and_then(|mut file| file.write_all(text.as_bytes()))
+                                          .map_err(|err| err.to_string())
+    }
+}
@@ -1,10 +1,12 @@
-use vindinium::{Tile, Board, Pos};
+use vindinium::{Tile, Board, Pos, Hero};
 use std::convert::From;
 use std::collections::LinkedList;
 use std::collections::HashMap;
+use std::collections::VecDeque;
 
 pub type Grid = Vec<Vec<Cell>>;
 pub type Path = LinkedList<Cell>;
+pub type DistanceMap = Vec<Vec<Option<usize>>>;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct IVector2 {
@@ -25,7 +27,9 @@ pub struct Cell {
     pub parent_pos: UVector2,
     pub f: usize,
     pub g: usize,
-    pub h: usize
+    pub h: usize,
+    pub blocked: bool,
+    pub cost: usize // added to g when stepping onto this cell
 }
 
 #[derive(Debug, Clone)]
@@ -58,7 +62,9 @@ impl<'a> From<&'a Board> for Map {
                         tile: tiles[x][y].clone(),
                         pos: pos.clone(),
                         parent_pos: pos.clone(),
-                        f: 0, g: 0, h: 0
+                        f: 0, g: 0, h: 0,
+                        blocked: false,
+                        cost: 0
                     });
             }
             cells.push(new_row);
@@ -68,6 +74,29 @@ impl<'a> From<&'a Board> for Map {
     }
 }
 
+impl Map {
+    /// Keeps paths from ever entering the cell at `pos`, including as a destination
+    pub fn block(&mut self, pos: &UVector2) {
+        if pos.x < self.size as usize && pos.y < self.size as usize {
+            self.grid[pos.x][pos.y].blocked = true;
+        }
+    }
+
+    /// Crashed heroes never move again, so their tiles stay walls for the rest of the game
+    pub fn block_crashed(&mut self, heroes: &[Hero]) {
+        for hero in heroes.iter().filter(|hero| hero.crashed) {
+            self.block(&UVector2::from(&hero.pos));
+        }
+    }
+
+    /// Makes paths through `pos` more expensive, in tenths of a step
+    pub fn add_cost(&mut self, pos: &UVector2, cost: usize) {
+        if pos.x < self.size as usize && pos.y < self.size as usize {
+            self.grid[pos.x][pos.y].cost += cost;
+        }
+    }
+}
+
 impl<'a> From<&'a UVector2> for IVector2 {
     fn from(vec: &'a UVector2) -> Self {
         IVector2 {x: vec.x as isize, y: vec.y as isize}
@@ -90,17 +119,17 @@ impl<'a> From<&'a Pos> for UVector2 {
     }
 }
 
-fn calc_neighbor(cp: &UVector2, ref_cell: &Cell, target_pos: &UVector2) -> Option<Cell> {
+fn calc_neighbor(cp: &UVector2, cp_g: usize, ref_cell: &Cell, target_pos: &UVector2) -> Option<Cell> {
     let mut cell = ref_cell.clone();
 
-    if (cell.tile != Tile::Free && cell.pos != *target_pos) ||
+    if cell.blocked || (cell.tile != Tile::Free && cell.pos != *target_pos) ||
         (cell.tile == Tile::Wood && cell.pos == *target_pos) {
 
         return None
     }
 
     cell.h = cell.pos.distance_from(&target_pos);
-    cell.g = 10;
+    cell.g = cp_g + 10 + cell.cost;
 
     // sum score
     cell.f = cell.g + cell.h;
@@ -111,7 +140,7 @@ fn calc_neighbor(cp: &UVector2, ref_cell: &Cell, target_pos: &UVector2) -> Optio
 
 /// Calculates f, g, and h values for each cell surrounding the `cp` parameter.
 /// - Returns surrounding neighbors with calculated values
-fn calc_neighbors(cp: &UVector2, target_pos: &UVector2, cells: &Grid, grid_size: usize) -> HashMap<UVector2, Cell> {
+fn calc_neighbors(cp: &UVector2, cp_g: usize, target_pos: &UVector2, cells: &Grid, grid_size: usize) -> HashMap<UVector2, Cell> {
     // ul-uv-ur
     // hl-cp-hr
     // dl-dv-dr
@@ -120,16 +149,16 @@ fn calc_neighbors(cp: &UVector2, target_pos: &UVector2, cells: &Grid, grid_size:
     let mut w_open_cells: Vec<Option<Cell>> = vec!();
 
     if i_cp.x - 1 >= 0 {
-        w_open_cells.push(calc_neighbor(cp, &cells[cp.x-1][cp.y], target_pos));
+        w_open_cells.push(calc_neighbor(cp, cp_g, &cells[cp.x-1][cp.y], target_pos));
     }
     if i_cp.x + 1 < i_grid_size {
-        w_open_cells.push(calc_neighbor(cp, &cells[cp.x+1][cp.y], target_pos));
+        w_open_cells.push(calc_neighbor(cp, cp_g, &cells[cp.x+1][cp.y], target_pos));
     }
     if i_cp.y - 1 >= 0 {
-        w_open_cells.push(calc_neighbor(cp, &cells[cp.x][cp.y-1], target_pos));
+        w_open_cells.push(calc_neighbor(cp, cp_g, &cells[cp.x][cp.y-1], target_pos));
     }
     if i_cp.y + 1 < i_grid_size {
-        w_open_cells.push(calc_neighbor(cp, &cells[cp.x][cp.y+1], target_pos));
+        w_open_cells.push(calc_neighbor(cp, cp_g, &cells[cp.x][cp.y+1], target_pos));
     }
 
     let mut map = HashMap::new();
@@ -165,7 +194,7 @@ pub fn gen_path(bot_pos: &UVector2, target_pos: &UVector2, map: &Map) -> Option<
         }
 
         // calculate and get neighbors to current cell
-        let mut neighbors = calc_neighbors(&best_node.pos, &target_pos, &path_grid, map.size as usize);
+        let mut neighbors = calc_neighbors(&best_node.pos, best_node.g, &target_pos, &path_grid, map.size as usize);
 
         // pop most optimal node of open cells and add to closed cells
         open_nodes.remove(&best_node.pos);
@@ -231,4 +260,40 @@ pub fn gen_path(bot_pos: &UVector2, target_pos: &UVector2, map: &Map) -> Option<
     }
 
     Some(path)
-}
\ No newline at end of file
+}
+
+/// Number of moves needed to reach every cell from `start`, or `None` if unreachable.
+/// Only free cells can be walked through; mines, taverns and heroes can be reached but not crossed.
+pub fn distance_map(start: &UVector2, map: &Map) -> DistanceMap {
+    let size = map.size as usize;
+    let mut distances: DistanceMap = vec![vec![None; size]; size];
+    let mut queue: VecDeque<UVector2> = VecDeque::new();
+    if start.x >= size || start.y >= size {
+        return distances
+    }
+
+    distances[start.x][start.y] = Some(0);
+    queue.push_back(start.clone());
+    while let Some(pos) = queue.pop_front() {
+        let dist = distances[pos.x][pos.y].unwrap();
+        if pos != *start && map.grid[pos.x][pos.y].tile != Tile::Free {
+            continue;
+        }
+
+        let i_pos = IVector2::from(&pos);
+        let neighbors = [(i_pos.x - 1, i_pos.y), (i_pos.x + 1, i_pos.y),
+                         (i_pos.x, i_pos.y - 1), (i_pos.x, i_pos.y + 1)];
+        for &(x, y) in neighbors.iter() {
+            if x < 0 || y < 0 || x >= size as isize || y >= size as isize {
+                continue;
+            }
+            let cell = &map.grid[x as usize][y as usize];
+            if cell.blocked || cell.tile == Tile::Wood || distances[cell.pos.x][cell.pos.y].is_some() {
+                continue;
+            }
+            distances[cell.pos.x][cell.pos.y] = Some(dist + 1);
+            queue.push_back(cell.pos.clone());
+        }
+    }
+    distances
+}
@@ -0,0 +1,189 @@
+use std::cmp;
+use vindinium::{State, Tile};
+use pathing;
+use pathing::{UVector2, DistanceMap, Map};
+use mines::MineValue;
+use combat;
+use endgame;
+use tavern;
+
+const MAX_ROUTE_MINES: usize = 6; // keeps the exhaustive search at a few thousand routes
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopKind {
+    Mine,
+    Tavern
+}
+
+#[derive(Debug, Clone)]
+pub struct Stop {
+    pub pos: UVector2,
+    pub kind: StopKind,
+    pub arrival: usize // In turns from now
+}
+
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub stops: Vec<Stop>,
+    pub turns: usize,
+    pub score: f32
+}
+
+#[derive(Debug, Clone)]
+struct Walker {
+    at: Option<usize>, // index into the distance maps, `None` while at our start position
+    life: isize,
+    gold: isize,
+    mine_count: isize,
+    turn: usize,
+    stops: Vec<Stop>,
+    score: f32
+}
+
+struct Planner<'a> {
+    start: &'a DistanceMap,
+    maps: Vec<DistanceMap>, // mines first, then taverns
+    points: Vec<UVector2>,
+    mine_count: usize,
+    turns_left: f32
+}
+
+impl<'a> Planner<'a> {
+    /// Moves from the walker's position to `to`. Finishing a leg leaves us next to the
+    /// mine or tavern we just used, one step closer to everything than the tile itself.
+    fn steps(&self, from: Option<usize>, to: usize) -> Option<usize> {
+        let target = &self.points[to];
+        match from {
+            None => self.start[target.x][target.y],
+            Some(i) => self.maps[i][target.x][target.y].map(|d| cmp::max(1, d - 1))
+        }
+    }
+
+    fn walk(&self, walker: &Walker, steps: usize) -> Walker {
+        let mut next = walker.clone();
+        next.turn += steps;
+        next.gold += walker.mine_count * steps as isize;
+        next.life = tavern::life_after_walk(walker.life, steps - 1);
+        next
+    }
+
+    /// Heals at the tavern reachable with the shortest detour on the way to `mine`
+    fn detour(&self, walker: &Walker, mine: usize) -> Option<Walker> {
+        let mut best: Option<(usize, usize, usize)> = None;
+        for t in self.mine_count..self.points.len() {
+            match (self.steps(walker.at, t), self.steps(Some(t), mine)) {
+                (Some(to_tavern), Some(to_mine)) => {
+                    let total = to_tavern + to_mine;
+                    if best.map_or(true, |(_, _, b)| total < b) {
+                        best = Some((t, to_tavern, total));
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        let (t, to_tavern, _) = match best {
+            Some(b) => b,
+            None => return None
+        };
+        let mut next = self.walk(walker, to_tavern);
+        let (heals, life) = tavern::heals_needed(next.life, next.gold, tavern::MAX_LIFE);
+        if heals == 0 {
+            return None
+        }
+        // the move onto the tavern buys the first heal
+        next.turn += heals - 1;
+        next.gold += walker.mine_count * (heals as isize - 1) - tavern::HEAL_COST * heals as isize;
+        next.life = life;
+        next.at = Some(t);
+        next.score -= (tavern::HEAL_COST * heals as isize) as f32;
+        next.stops.push(Stop {pos: self.points[t].clone(), kind: StopKind::Tavern, arrival: next.turn});
+        Some(next)
+    }
+
+    fn capture(&self, walker: &Walker, mine: usize) -> Option<Walker> {
+        let steps = match self.steps(walker.at, mine) {
+            Some(steps) => steps,
+            None => return None
+        };
+        let mut next = self.walk(walker, steps);
+        if next.life <= combat::MINE_DAMAGE {
+            return None
+        }
+        next.life = cmp::max(1, next.life - combat::MINE_DAMAGE - combat::THIRST_DAMAGE);
+        next.mine_count += 1;
+        next.at = Some(mine);
+        next.score += (self.turns_left - next.turn as f32).max(0.0);
+        next.stops.push(Stop {pos: self.points[mine].clone(), kind: StopKind::Mine, arrival: next.turn});
+        Some(next)
+    }
+
+    fn search(&self, walker: &Walker, visited: &mut Vec<bool>, best: &mut Option<Walker>) {
+        let improves = match *best {
+            Some(ref b) => walker.score > b.score || (walker.score == b.score && walker.turn < b.turn),
+            None => true
+        };
+        if improves && !walker.stops.is_empty() {
+            *best = Some(walker.clone());
+        }
+
+        for mine in 0..self.mine_count {
+            if visited[mine] {
+                continue;
+            }
+            let w_next = match self.capture(walker, mine) {
+                Some(next) => Some(next),
+                None => self.detour(walker, mine).and_then(|healed| self.capture(&healed, mine))
+            };
+            match w_next {
+                Some(next) => {
+                    visited[mine] = true;
+                    self.search(&next, visited, best);
+                    visited[mine] = false;
+                },
+                None => {}
+            }
+        }
+    }
+}
+
+/// Orders the most valuable of `candidates` into the route that earns the most gold
+/// before the game ends, stopping at taverns whenever life would not cover the next capture.
+pub fn plan(state: &State, map: &Map, distances: &DistanceMap, candidates: &[MineValue]) -> Option<Route> {
+    let mut points = candidates.iter()
+                               .take(MAX_ROUTE_MINES)
+                               .map(|mine| mine.pos.clone())
+                               .collect::<Vec<UVector2>>();
+    let mine_count = points.len();
+    if mine_count == 0 {
+        return None
+    }
+    for (x, row) in state.game.board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            if *tile == Tile::Tavern {
+                points.push(UVector2 {x: x, y: y});
+            }
+        }
+    }
+
+    let planner = Planner {
+        start: distances,
+        maps: points.iter().map(|pos| pathing::distance_map(pos, map)).collect(),
+        points: points,
+        mine_count: mine_count,
+        turns_left: endgame::turns_left(state, state.hero.id) as f32
+    };
+    let walker = Walker {
+        at: None,
+        life: state.hero.life,
+        gold: state.hero.gold,
+        mine_count: state.hero.mine_count,
+        turn: 0,
+        stops: vec!(),
+        score: 0.0
+    };
+
+    let mut best: Option<Walker> = None;
+    planner.search(&walker, &mut vec![false; mine_count], &mut best);
+    best.map(|walker| Route {stops: walker.stops, turns: walker.turn, score: walker.score})
+}
@@ -0,0 +1,126 @@
+use std::cmp;
+use vindinium::{State, Tile, Dir, Hero};
+use pathing;
+use pathing::{UVector2, Map};
+use opponent::DIRS;
+use combat;
+use spawn;
+use tavern;
+use trace::Decision;
+
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub dir: Dir,
+    pub pos: UVector2,
+    pub life: isize,
+    pub threats: usize, // enemies able to hit us before our next move
+    pub fatal: bool
+}
+
+/// Where we stand after moving in `dir`. Mines, taverns, heroes and walls stop us in place.
+fn landing(pos: &UVector2, dir: &Dir, state: &State) -> (UVector2, Option<Tile>) {
+    let (dx, dy) = dir.offset();
+    let x = pos.x as isize + dx;
+    let y = pos.y as isize + dy;
+    let size = state.game.board.size as isize;
+    if *dir == Dir::Stay || x < 0 || y < 0 || x >= size || y >= size {
+        return (pos.clone(), None)
+    }
+    let tile = state.game.board.tiles[x as usize][y as usize].clone();
+    match tile {
+        Tile::Free => (UVector2 {x: x as usize, y: y as usize}, None),
+        other => (pos.clone(), Some(other))
+    }
+}
+
+/// Plays our move in `dir` and everything the enemies can do to us before our next turn.
+/// With `telefrag`, dying on a mine to respawn onto a richer enemy camping our spawn is no loss.
+pub fn simulate(state: &State, map: &Map, dir: &Dir, telefrag: bool) -> Outcome {
+    let hero_pos = UVector2::from(&state.hero.pos);
+    let (pos, bumped) = landing(&hero_pos, dir, state);
+    let mut life = state.hero.life;
+    let mut fatal = false;
+    match bumped {
+        Some(Tile::Mine(owner)) if owner != Some(state.hero.id) => {
+            // dying here only pays off if we respawn onto a richer enemy
+            let telefrag = telefrag && spawn::telefrag_target(state).map_or(false, |victim| {
+                victim.mine_count > state.hero.mine_count
+            });
+            if life <= combat::MINE_DAMAGE && !telefrag {
+                fatal = true;
+            }
+            life -= combat::MINE_DAMAGE;
+        },
+        Some(Tile::Tavern) if state.hero.gold >= tavern::HEAL_COST => {
+            life = cmp::min(tavern::MAX_LIFE, life + tavern::HEAL_LIFE);
+        },
+        _ => {}
+    }
+    if spawn::unsafe_spawns(state).contains(&pos) {
+        fatal = true;
+    }
+
+    // we strike every adjacent enemy right after moving, then thirst sets in
+    let reach = pathing::distance_map(&pos, map);
+    let enemies = state.game.heroes.iter().filter(|enemy| {
+        enemy.id != state.hero.id && !enemy.crashed
+    }).map(|enemy| {
+        let mut enemy = enemy.clone();
+        if reach[enemy.pos.x as usize][enemy.pos.y as usize] == Some(1) {
+            enemy.life -= combat::ATTACK_DAMAGE;
+        }
+        enemy
+    }).filter(|enemy| enemy.life > 0).collect::<Vec<Hero>>();
+    life = cmp::max(1, life - combat::THIRST_DAMAGE);
+
+    let threats = enemies.iter().filter(|enemy| {
+        match reach[enemy.pos.x as usize][enemy.pos.y as usize] {
+            Some(d) => d <= 2,
+            None => false
+        }
+    }).collect::<Vec<&Hero>>();
+
+    let mut after = state.hero.clone();
+    after.life = life;
+    if life - combat::ATTACK_DAMAGE * threats.len() as isize <= 0 {
+        fatal = true;
+    }
+    if threats.iter().any(|enemy| !combat::duel(enemy, &after).survives(after.id)) {
+        fatal = true;
+    }
+
+    Outcome {dir: dir.clone(), pos: pos, life: life, threats: threats.len(), fatal: fatal}
+}
+
+pub fn is_safe(state: &State, map: &Map, dir: &Dir, telefrag: bool) -> bool {
+    !simulate(state, map, dir, telefrag).fatal
+}
+
+/// Lets `dir` through if it cannot get us killed. Otherwise substitutes the safe move that
+/// leaves us least exposed, preferring to keep moving, or the least deadly move if none is safe.
+pub fn filter(state: &State, map: &Map, dir: Dir, telefrag: bool) -> Dir {
+    let chosen = simulate(state, map, &dir, telefrag);
+    if !chosen.fatal {
+        return dir
+    }
+
+    let outcomes = DIRS.iter().map(|d| simulate(state, map, d, telefrag)).collect::<Vec<Outcome>>();
+    let hero_pos = UVector2::from(&state.hero.pos);
+    let safe = outcomes.iter().filter(|o| !o.fatal).min_by_key(|o| (o.threats, -o.life, o.pos == hero_pos));
+    match safe {
+        Some(outcome) => outcome.dir.clone(),
+        None => {
+            let least_deadly = outcomes.iter().min_by_key(|o| (o.threats, -o.life)).unwrap();
+            least_deadly.dir.clone()
+        }
+    }
+}
+
+/// `filter`, noting in `decision` the move it turned down, if any
+pub fn filter_noted(state: &State, map: &Map, dir: Dir, telefrag: bool, decision: &mut Decision) -> Dir {
+    let safe = filter(state, map, dir.clone(), telefrag);
+    if safe != dir {
+        decision.vetoed = Some(dir);
+    }
+    safe
+}
@@ -0,0 +1,282 @@
+extern crate rand;
+use self::rand::{Rng, SeedableRng, StdRng};
+use std::cmp;
+use vindinium::{State, Game, Board, Hero, Pos, Tile, Dir, HeroId};
+use pathing;
+use pathing::{UVector2, Map};
+use combat;
+use tavern;
+
+const MAP_SIZE: usize = 18; // In tiles, always even so the map mirrors cleanly
+const MAX_TURNS: isize = 1200; // game turns, 300 moves per hero
+pub const HERO_COUNT: usize = 4;
+const WOOD_CHANCE: f32 = 0.3;
+const MINE_CHANCE: f32 = 0.05;
+
+/// Anything that can pick a move for the hero in `state`
+pub trait Player {
+    fn play(&mut self, state: &State) -> Dir;
+}
+
+/// Plays Vindinium games locally, following the rules of the official server
+#[derive(Debug, Clone)]
+pub struct Simulator {
+    pub game: Game
+}
+
+fn mirror(pos: &UVector2, quadrant: usize, size: usize) -> UVector2 {
+    match quadrant {
+        0 => pos.clone(),
+        1 => UVector2 {x: size - 1 - pos.x, y: pos.y},
+        2 => UVector2 {x: size - 1 - pos.x, y: size - 1 - pos.y},
+        _ => UVector2 {x: pos.x, y: size - 1 - pos.y}
+    }
+}
+
+fn random_free(rng: &mut StdRng, tiles: &[Vec<Tile>], half: usize) -> UVector2 {
+    loop {
+        let pos = UVector2 {x: rng.gen_range(0, half), y: rng.gen_range(0, half)};
+        if tiles[pos.x][pos.y] == Tile::Free {
+            return pos
+        }
+    }
+}
+
+/// A random board with four-fold symmetry, so no spawn has the upper hand. Every mine and
+/// tavern can be reached from every spawn.
+fn generate(rng: &mut StdRng) -> (Board, Vec<UVector2>) {
+    let size = MAP_SIZE;
+    let half = size / 2;
+    loop {
+        let mut tiles = vec![vec![Tile::Free; size]; size];
+        for x in 0..half {
+            for y in 0..half {
+                let roll = rng.gen::<f32>();
+                tiles[x][y] = if roll < WOOD_CHANCE {
+                    Tile::Wood
+                } else if roll < WOOD_CHANCE + MINE_CHANCE {
+                    Tile::Mine(None)
+                } else {
+                    Tile::Free
+                };
+            }
+        }
+        let tavern = random_free(rng, &tiles, half);
+        tiles[tavern.x][tavern.y] = Tile::Tavern;
+        let spawn = random_free(rng, &tiles, half);
+
+        for x in 0..half {
+            for y in 0..half {
+                let tile = tiles[x][y].clone();
+                for quadrant in 1..HERO_COUNT {
+                    let pos = mirror(&UVector2 {x: x, y: y}, quadrant, size);
+                    tiles[pos.x][pos.y] = tile.clone();
+                }
+            }
+        }
+        let spawns = (0..HERO_COUNT).map(|quadrant| mirror(&spawn, quadrant, size)).collect::<Vec<UVector2>>();
+        for (i, pos) in spawns.iter().enumerate() {
+            tiles[pos.x][pos.y] = Tile::Hero(i as HeroId + 1);
+        }
+
+        let board = Board {size: size, tiles: tiles};
+        let distances = pathing::distance_map(&spawns[0], &Map::from(&board));
+        let connected = board.tiles.iter().enumerate().all(|(x, row)| {
+            row.iter().enumerate().all(|(y, tile)| match *tile {
+                Tile::Wood | Tile::Free => true,
+                _ => distances[x][y].is_some()
+            })
+        });
+        let mines = board.tiles.iter().flat_map(|row| row.iter()).filter(|tile| match **tile {
+            Tile::Mine(_) => true,
+            _ => false
+        }).count();
+        if connected && mines > 0 {
+            return (board, spawns)
+        }
+    }
+}
+
+impl Simulator {
+    /// A fresh game on a random map. The same seed always gives the same map.
+    pub fn new(seed: usize) -> Simulator {
+        let mut rng: StdRng = SeedableRng::from_seed(&[seed][..]);
+        let (board, spawns) = generate(&mut rng);
+        let heroes = spawns.iter().enumerate().map(|(i, spawn)| {
+            let pos = Pos {x: spawn.x as isize, y: spawn.y as isize};
+            Hero {
+                id: i as HeroId + 1,
+                name: format!("sim{}", i + 1),
+                user_id: None,
+                elo: None,
+                pos: pos.clone(),
+                life: tavern::MAX_LIFE,
+                gold: 0,
+                mine_count: 0,
+                spawn_pos: pos,
+                crashed: false
+            }
+        }).collect();
+        Simulator::from_game(Game {
+            id: format!("sim-{}", seed),
+            turn: 0,
+            max_turns: MAX_TURNS,
+            heroes: heroes,
+            board: board,
+            finished: false
+        })
+    }
+
+    /// Continues a game from any point, such as a state saved from the real server
+    pub fn from_game(game: Game) -> Simulator {
+        Simulator {game: game}
+    }
+
+    /// The hero whose move is next
+    pub fn to_move(&self) -> HeroId {
+        self.game.turn % self.game.heroes.len() as isize + 1
+    }
+
+    /// The game as `hero_id` would receive it from the server
+    pub fn state_for(&self, hero_id: HeroId) -> State {
+        State {
+            game: self.game.clone(),
+            hero: self.game.heroes[hero_id as usize - 1].clone(),
+            token: String::new(),
+            view_url: String::new(),
+            play_url: String::new()
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.game.finished
+    }
+
+    fn hero_at(&self, pos: &UVector2) -> Option<HeroId> {
+        self.game.heroes.iter().find(|hero| UVector2::from(&hero.pos) == *pos).map(|hero| hero.id)
+    }
+
+    fn count_mines(&mut self) {
+        for hero in self.game.heroes.iter_mut() {
+            hero.mine_count = self.game.board.tiles.iter().flat_map(|row| row.iter()).filter(|tile| {
+                **tile == Tile::Mine(Some(hero.id))
+            }).count() as isize;
+        }
+    }
+
+    /// Hands every mine of `from` to `to`, or back to the goblins
+    fn transfer_mines(&mut self, from: HeroId, to: Option<HeroId>) {
+        for row in self.game.board.tiles.iter_mut() {
+            for tile in row.iter_mut() {
+                if *tile == Tile::Mine(Some(from)) {
+                    *tile = Tile::Mine(to);
+                }
+            }
+        }
+        self.count_mines();
+    }
+
+    /// Kills `victim`, and respawns it, killing in turn whoever stands on its spawn
+    fn kill(&mut self, victim: HeroId, killer: Option<HeroId>) {
+        self.transfer_mines(victim, killer);
+        let i = victim as usize - 1;
+        let old = UVector2::from(&self.game.heroes[i].pos);
+        let spawn = UVector2::from(&self.game.heroes[i].spawn_pos);
+        if self.game.board.tiles[old.x][old.y] == Tile::Hero(victim) {
+            self.game.board.tiles[old.x][old.y] = Tile::Free;
+        }
+        let w_occupant = self.hero_at(&spawn).filter(|other| *other != victim);
+        {
+            let hero = &mut self.game.heroes[i];
+            hero.pos = Pos {x: spawn.x as isize, y: spawn.y as isize};
+            hero.life = tavern::MAX_LIFE;
+        }
+        self.game.board.tiles[spawn.x][spawn.y] = Tile::Hero(victim);
+        match w_occupant {
+            Some(occupant) => self.kill(occupant, Some(victim)),
+            None => {}
+        }
+    }
+
+    /// Plays `dir` for the hero to move: walk or bump, fight, then collect gold and thirst
+    pub fn step(&mut self, dir: &Dir) {
+        if self.game.finished {
+            return
+        }
+        let id = self.to_move();
+        let i = id as usize - 1;
+        let size = self.game.board.size as isize;
+        let pos = UVector2::from(&self.game.heroes[i].pos);
+        let (dx, dy) = dir.offset();
+        let (x, y) = (pos.x as isize + dx, pos.y as isize + dy);
+
+        let mut died = false;
+        if *dir != Dir::Stay && x >= 0 && y >= 0 && x < size && y < size {
+            let (x, y) = (x as usize, y as usize);
+            match self.game.board.tiles[x][y].clone() {
+                Tile::Free => {
+                    self.game.board.tiles[pos.x][pos.y] = Tile::Free;
+                    self.game.board.tiles[x][y] = Tile::Hero(id);
+                    self.game.heroes[i].pos = Pos {x: x as isize, y: y as isize};
+                },
+                Tile::Tavern => {
+                    let hero = &mut self.game.heroes[i];
+                    if hero.gold >= tavern::HEAL_COST {
+                        hero.gold -= tavern::HEAL_COST;
+                        hero.life = cmp::min(tavern::MAX_LIFE, hero.life + tavern::HEAL_LIFE);
+                    }
+                },
+                Tile::Mine(owner) if owner != Some(id) => {
+                    if self.game.heroes[i].life > combat::MINE_DAMAGE {
+                        self.game.heroes[i].life -= combat::MINE_DAMAGE;
+                        self.game.board.tiles[x][y] = Tile::Mine(Some(id));
+                        self.count_mines();
+                    } else {
+                        self.kill(id, None);
+                        died = true;
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        // the mover strikes every enemy next to wherever it ended up
+        let here = UVector2::from(&self.game.heroes[i].pos);
+        let victims = self.game.heroes.iter().filter(|hero| {
+            !died && hero.id != id && UVector2::from(&hero.pos).distance_from(&here) == 10
+        }).map(|hero| hero.id).collect::<Vec<HeroId>>();
+        for victim in victims {
+            let j = victim as usize - 1;
+            self.game.heroes[j].life -= combat::ATTACK_DAMAGE;
+            if self.game.heroes[j].life <= 0 {
+                self.kill(victim, Some(id));
+            }
+        }
+
+        let hero = &mut self.game.heroes[i];
+        hero.gold += hero.mine_count;
+        hero.life = cmp::max(1, hero.life - combat::THIRST_DAMAGE);
+
+        self.game.turn += 1;
+        if self.game.turn >= self.game.max_turns {
+            self.game.finished = true;
+        }
+    }
+
+    /// Plays to the end with `players[i]` controlling hero `i + 1`, returning the final heroes
+    pub fn run<P: Player>(&mut self, players: &mut [P]) -> Vec<Hero> {
+        while !self.game.finished {
+            let id = self.to_move();
+            let state = self.state_for(id);
+            let dir = players[id as usize - 1].play(&state);
+            self.step(&dir);
+        }
+        self.game.heroes.clone()
+    }
+}
+
+/// Final placing of `hero_id` by gold, starting at 1. Ties share the better place.
+pub fn rank(heroes: &[Hero], hero_id: HeroId) -> usize {
+    let gold = heroes.iter().find(|hero| hero.id == hero_id).map_or(0, |hero| hero.gold);
+    heroes.iter().filter(|hero| hero.gold > gold).count() + 1
+}
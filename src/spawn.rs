@@ -0,0 +1,66 @@
+use vindinium::{State, Hero, HeroId, Tile, Pos};
+use pathing::UVector2;
+use combat;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnPoint {
+    pub hero_id: HeroId,
+    pub pos: UVector2
+}
+
+fn steps_between(a: &Pos, b: &Pos) -> usize {
+    ((a.x - b.x).abs() + (a.y - b.y).abs()) as usize
+}
+
+fn mine_adjacent(hero: &Hero, state: &State) -> bool {
+    let size = state.game.board.size as isize;
+    let neighbors = [(hero.pos.x - 1, hero.pos.y), (hero.pos.x + 1, hero.pos.y),
+                     (hero.pos.x, hero.pos.y - 1), (hero.pos.x, hero.pos.y + 1)];
+    neighbors.iter().any(|&(x, y)| {
+        if x < 0 || y < 0 || x >= size || y >= size {
+            return false
+        }
+        match state.game.board.tiles[x as usize][y as usize] {
+            Tile::Mine(Some(owner)) => owner != hero.id,
+            Tile::Mine(None) => true,
+            _ => false
+        }
+    })
+}
+
+pub fn spawn_points(state: &State) -> Vec<SpawnPoint> {
+    state.game.heroes.iter().map(|hero| SpawnPoint {
+        hero_id: hero.id,
+        pos: UVector2::from(&hero.spawn_pos)
+    }).collect()
+}
+
+/// Whether `hero` could be killed before our next move, either by another hero
+/// closing in or by walking into a mine it cannot afford
+pub fn about_to_die(hero: &Hero, state: &State) -> bool {
+    if hero.crashed {
+        return false
+    }
+    let threatened = hero.life <= combat::ATTACK_DAMAGE && state.game.heroes.iter().any(|other| {
+        other.id != hero.id && steps_between(&other.pos, &hero.pos) <= 2
+    });
+    let suicidal = hero.life <= combat::MINE_DAMAGE && mine_adjacent(hero, state);
+    threatened || suicidal
+}
+
+/// Spawn tiles of enemies that may respawn before our next move. Standing on one of
+/// these when its owner respawns kills us and hands our mines to that enemy.
+pub fn unsafe_spawns(state: &State) -> Vec<UVector2> {
+    state.game.heroes.iter()
+                     .filter(|hero| hero.id != state.hero.id && about_to_die(hero, state))
+                     .map(|hero| UVector2::from(&hero.spawn_pos))
+                     .collect()
+}
+
+/// An enemy standing on our spawn that we would telefrag, with its mines, if we died now
+pub fn telefrag_target(state: &State) -> Option<&Hero> {
+    let spawn = UVector2::from(&state.hero.spawn_pos);
+    state.game.heroes.iter()
+                     .filter(|hero| hero.id != state.hero.id && UVector2::from(&hero.pos) == spawn)
+                     .next()
+}
@@ -0,0 +1,111 @@
+use std::collections::VecDeque;
+use vindinium::{State, Hero};
+use pathing::UVector2;
+use camping;
+
+const WINDOW: usize = 8; // own turns of history we look back over
+const STALL_TURNS: usize = 4; // turns standing still without a reason
+const MAX_PLACES: usize = 2; // bouncing between this few tiles for a whole window is oscillating
+const BAN_TURNS: isize = 40; // game turns a goal stays off the table, about ten of our own
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stuck {
+    Stalled,
+    Oscillating,
+    FlipFlop(UVector2, UVector2)
+}
+
+/// Notices when we bounce between the same few tiles or goals without getting anywhere
+#[derive(Debug, Clone)]
+pub struct MotionTracker {
+    positions: VecDeque<(UVector2, bool)>, // where we ended each turn, and whether we had a reason to be there
+    goals: VecDeque<Option<UVector2>>,
+    last: Option<Hero>,
+    still: usize,
+    banned: Vec<(UVector2, isize)>
+}
+
+impl MotionTracker {
+    pub fn new() -> MotionTracker {
+        MotionTracker {
+            positions: VecDeque::new(),
+            goals: VecDeque::new(),
+            last: None,
+            still: 0,
+            banned: vec!()
+        }
+    }
+
+    /// Records where our last move left us. Staying put is fine while we heal, capture, fight or camp.
+    pub fn observe(&mut self, state: &State) {
+        let hero = &state.hero;
+        let pos = UVector2::from(&hero.pos);
+        let camping = camping::at_tavern(state, &pos).is_some();
+        let reason = camping || match self.last {
+            Some(ref last) => hero.life > last.life || hero.mine_count > last.mine_count
+                || state.game.heroes.iter().any(|other| {
+                    other.id != hero.id && (other.pos.x - hero.pos.x).abs() + (other.pos.y - hero.pos.y).abs() == 1
+                }),
+            None => true
+        };
+        let stayed = self.positions.back().map_or(false, |&(ref last_pos, _)| *last_pos == pos);
+        self.still = if stayed && !reason { self.still + 1 } else { 0 };
+
+        self.positions.push_back((pos, reason));
+        while self.positions.len() > WINDOW {
+            self.positions.pop_front();
+        }
+        self.last = Some(hero.clone());
+        let turn = state.game.turn;
+        self.banned.retain(|&(_, until)| until > turn);
+    }
+
+    pub fn record_goal(&mut self, goal: Option<UVector2>) {
+        self.goals.push_back(goal);
+        while self.goals.len() > WINDOW {
+            self.goals.pop_front();
+        }
+    }
+
+    pub fn check(&self) -> Option<Stuck> {
+        if self.still >= STALL_TURNS {
+            return Some(Stuck::Stalled)
+        }
+
+        let goals = self.goals.iter().rev().take(4).cloned().collect::<Vec<Option<UVector2>>>();
+        if goals.len() == 4 {
+            match (&goals[0], &goals[1], &goals[2], &goals[3]) {
+                (&Some(ref a), &Some(ref b), &Some(ref c), &Some(ref d)) if a == c && b == d && a != b => {
+                    return Some(Stuck::FlipFlop(a.clone(), b.clone()))
+                },
+                _ => {}
+            }
+        }
+
+        if self.positions.len() == WINDOW {
+            // turns spent healing, fighting or camping don't count as going nowhere
+            let mut places: Vec<&UVector2> = vec!();
+            for &(ref pos, reason) in &self.positions {
+                if !reason && !places.contains(&pos) {
+                    places.push(pos);
+                }
+            }
+            if places.len() >= 2 && places.len() <= MAX_PLACES {
+                return Some(Stuck::Oscillating)
+            }
+        }
+        None
+    }
+
+    /// Keeps `goal` out of consideration for a while, and starts watching afresh
+    pub fn ban(&mut self, goal: &UVector2, turn: isize) {
+        self.banned.push((goal.clone(), turn + BAN_TURNS));
+        self.positions.clear();
+        self.goals.clear();
+        self.still = 0;
+    }
+
+    pub fn banned(&self) -> Vec<UVector2> {
+        self.banned.iter().map(|&(ref pos, _)| pos.clone()).collect()
+    }
+}
@@ -0,0 +1,98 @@
+use std::cmp;
+use vindinium::{State, Tile};
+use pathing;
+use pathing::{UVector2, Map, DistanceMap};
+use combat;
+use endgame;
+
+pub const HEAL_COST: isize = 2; // gold
+pub const HEAL_LIFE: isize = 50;
+pub const MAX_LIFE: isize = 100;
+pub const LIFE_GOLD: f32 = HEAL_COST as f32 / HEAL_LIFE as f32; // what a point of life costs at a tavern
+const AMBUSH_PENALTY: f32 = 20.0; // In turns, per enemy that could beat us to the tavern and win
+
+#[derive(Debug, Clone)]
+pub struct TavernPlan {
+    pub pos: UVector2,
+    pub steps: usize,
+    pub life_on_arrival: isize,
+    pub heals: usize,
+    pub life_after: isize,
+    pub risk: f32
+}
+
+/// Life after walking `steps` tiles, losing one point of thirst per move
+pub fn life_after_walk(life: isize, steps: usize) -> isize {
+    cmp::max(1, life - steps as isize * combat::THIRST_DAMAGE)
+}
+
+/// Consecutive heals to buy so that life reaches `target_life` without wasting a heal on
+/// a full bar, limited by `gold`. Each heal also costs a turn of thirst.
+/// - Returns the number of heals and the life we leave with
+pub fn heals_needed(life: isize, gold: isize, target_life: isize) -> (usize, isize) {
+    let mut life = life;
+    let mut gold = gold;
+    let mut heals = 0;
+    while gold >= HEAL_COST && (life < target_life || life + HEAL_LIFE <= MAX_LIFE) {
+        life = cmp::max(1, cmp::min(MAX_LIFE, life + HEAL_LIFE) - combat::THIRST_DAMAGE);
+        gold -= HEAL_COST;
+        heals += 1;
+        if life >= MAX_LIFE - combat::THIRST_DAMAGE {
+            break;
+        }
+    }
+    (heals, life)
+}
+
+/// Picks the tavern that gets us to `target_life` with the least risk.
+/// Taverns we cannot reach, pay for or get any use of before the game ends are never chosen.
+pub fn plan(state: &State, map: &Map, distances: &DistanceMap, target_life: isize) -> Option<TavernPlan> {
+    if state.hero.gold < HEAL_COST {
+        return None
+    }
+    let turns_left = endgame::turns_left(state, state.hero.id);
+    let enemies = state.game.heroes.iter().filter(|enemy| enemy.id != state.hero.id && !enemy.crashed).map(|enemy| {
+        (enemy, pathing::distance_map(&UVector2::from(&enemy.pos), map))
+    }).collect::<Vec<_>>();
+
+    let mut best: Option<TavernPlan> = None;
+    for (x, row) in state.game.board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            if *tile != Tile::Tavern {
+                continue;
+            }
+            // life bought on the last move, or after it, is never used
+            let steps = match distances[x][y] {
+                Some(steps) if (steps as isize) < turns_left => steps,
+                _ => continue
+            };
+            let pos = UVector2 {x: x, y: y};
+            // we pay thirst on every move but the one that buys the first heal
+            let life_on_arrival = life_after_walk(state.hero.life, cmp::max(1, steps) - 1);
+            let mut arriving = state.hero.clone();
+            arriving.life = life_on_arrival;
+
+            // an enemy there before us strikes first
+            let ambushers = enemies.iter().filter(|&&(enemy, ref reach)| match reach[x][y] {
+                Some(enemy_steps) => enemy_steps <= steps && !combat::duel(enemy, &arriving).survives(state.hero.id),
+                None => false
+            }).count();
+
+            let budget = cmp::min(state.hero.gold, (turns_left - steps as isize) * HEAL_COST);
+            let (heals, life_after) = heals_needed(life_on_arrival, budget, target_life);
+            let tavern_plan = TavernPlan {
+                pos: pos,
+                steps: steps,
+                life_on_arrival: life_on_arrival,
+                heals: heals,
+                life_after: life_after,
+                risk: steps as f32 + ambushers as f32 * AMBUSH_PENALTY
+            };
+            best = match best {
+                Some(ref current) if current.risk <= tavern_plan.risk => Some(current.clone()),
+                _ => Some(tavern_plan)
+            };
+        }
+    }
+    best
+}
@@ -0,0 +1,128 @@
+use vindinium::{State, Tile, HeroId};
+use pathing;
+use pathing::{UVector2, Map};
+use combat;
+
+/// A runner-up this many turns behind the winner still makes a tile contested, in game turns
+const CONTEST_MARGIN: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct Claim {
+    pub owner: HeroId,
+    pub moves: usize, // moves the owner needs to get there
+    pub arrival: usize, // game turns until the owner gets there
+    pub contested: bool
+}
+
+#[derive(Debug, Clone)]
+pub struct HeroTerritory {
+    pub hero_id: HeroId,
+    pub tiles: usize,
+    pub mines: Vec<UVector2>,
+    pub taverns: Vec<UVector2>,
+    pub contested_mines: Vec<UVector2>
+}
+
+/// Which hero reaches each tile first, taking turn order into account
+#[derive(Debug, Clone)]
+pub struct Territory {
+    pub claims: Vec<Vec<Option<Claim>>>,
+    pub heroes: Vec<HeroTerritory>
+}
+
+impl Territory {
+    pub fn analyze(state: &State, map: &Map) -> Territory {
+        let size = map.size as usize;
+        let order = combat::turn_order(&state.game.heroes, state.game.turn);
+        let hero_count = order.len();
+        let mut best: Vec<Vec<Vec<(usize, HeroId, usize)>>> = vec![vec![vec!(); size]; size];
+
+        for (slot, hero_id) in order.iter().enumerate() {
+            let hero = match state.game.heroes.iter().find(|hero| hero.id == *hero_id) {
+                Some(hero) if !hero.crashed => hero,
+                _ => continue
+            };
+            let reach = pathing::distance_map(&UVector2::from(&hero.pos), map);
+            for x in 0..size {
+                for y in 0..size {
+                    match reach[x][y] {
+                        // our k-th move comes `slot + (k - 1) * hero_count` turns from now
+                        Some(moves) => {
+                            let arrival = if moves == 0 { 0 } else { slot + (moves - 1) * hero_count };
+                            best[x][y].push((arrival, *hero_id, moves));
+                        },
+                        None => {}
+                    }
+                }
+            }
+        }
+
+        let mut claims: Vec<Vec<Option<Claim>>> = vec![vec![None; size]; size];
+        for x in 0..size {
+            for y in 0..size {
+                let arrivals = &mut best[x][y];
+                arrivals.sort();
+                claims[x][y] = arrivals.first().map(|&(arrival, owner, moves)| Claim {
+                    owner: owner,
+                    moves: moves,
+                    arrival: arrival,
+                    contested: arrivals.len() > 1 && arrivals[1].0 <= arrival + CONTEST_MARGIN
+                });
+            }
+        }
+
+        let mut heroes = state.game.heroes.iter().map(|hero| HeroTerritory {
+            hero_id: hero.id,
+            tiles: 0,
+            mines: vec!(),
+            taverns: vec!(),
+            contested_mines: vec!()
+        }).collect::<Vec<HeroTerritory>>();
+        for (x, row) in claims.iter().enumerate() {
+            for (y, w_claim) in row.iter().enumerate() {
+                let claim = match *w_claim {
+                    Some(ref claim) => claim,
+                    None => continue
+                };
+                let summary = match heroes.iter_mut().find(|h| h.hero_id == claim.owner) {
+                    Some(summary) => summary,
+                    None => continue
+                };
+                let pos = UVector2 {x: x, y: y};
+                match state.game.board.tiles[x][y] {
+                    Tile::Mine(_) => {
+                        if claim.contested {
+                            summary.contested_mines.push(pos.clone());
+                        }
+                        summary.mines.push(pos);
+                    },
+                    Tile::Tavern => summary.taverns.push(pos),
+                    Tile::Free => summary.tiles += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        Territory {claims: claims, heroes: heroes}
+    }
+
+    pub fn claim_at(&self, pos: &UVector2) -> Option<&Claim> {
+        self.claims[pos.x][pos.y].as_ref()
+    }
+
+    pub fn owner_at(&self, pos: &UVector2) -> Option<HeroId> {
+        self.claim_at(pos).map(|claim| claim.owner)
+    }
+
+    pub fn summary(&self, hero_id: HeroId) -> Option<&HeroTerritory> {
+        self.heroes.iter().find(|h| h.hero_id == hero_id)
+    }
+
+    /// Whether `hero_id` gets to `pos` first with nobody close behind
+    pub fn safe_claim(&self, pos: &UVector2, hero_id: HeroId) -> bool {
+        match self.claim_at(pos) {
+            Some(claim) => claim.owner == hero_id && !claim.contested,
+            None => false
+        }
+    }
+}
@@ -0,0 +1,110 @@
+extern crate rustc_serialize;
+use self::rustc_serialize::json;
+use self::rustc_serialize::json::Json;
+use std::fmt;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+use vindinium::{State, Dir, HeroId};
+use pathing::UVector2;
+
+/// A place we considered going to, with the value we gave it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub pos: UVector2,
+    pub score: f32
+}
+
+/// Why the bot made the move it made on one turn
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decision {
+    pub turn: isize,
+    pub hero_id: HeroId,
+    pub goal: Option<UVector2>,
+    pub reason: String,
+    pub candidates: Vec<Candidate>,
+    pub path_length: Option<usize>,
+    pub dir: Dir,
+    pub vetoed: Option<Dir>, // the move we meant to make, when it would have got us killed
+    pub millis: f32 // time spent deciding
+}
+
+fn pos_to_json(pos: &UVector2) -> Json {
+    Json::Array(vec!(Json::U64(pos.x as u64), Json::U64(pos.y as u64)))
+}
+
+impl Decision {
+    pub fn new(state: &State) -> Decision {
+        Decision {
+            turn: state.game.turn,
+            hero_id: state.hero.id,
+            goal: None,
+            reason: String::new(),
+            candidates: vec!(),
+            path_length: None,
+            dir: Dir::Stay,
+            vetoed: None,
+            millis: 0.0
+        }
+    }
+
+    /// Settles on `pos` for `reason`, for `find_destination` to return
+    pub fn choose(&mut self, pos: UVector2, reason: &str) -> Option<UVector2> {
+        self.goal = Some(pos.clone());
+        self.reason = reason.to_string();
+        Some(pos)
+    }
+
+    pub fn set_time(&mut self, elapsed: Duration) {
+        self.millis = elapsed.as_secs() as f32 * 1000.0 + elapsed.subsec_nanos() as f32 / 1000000.0;
+    }
+
+    pub fn to_json(&self) -> Json {
+        let mut obj: json::Object = json::Object::new();
+        obj.insert("turn".to_string(), Json::I64(self.turn as i64));
+        obj.insert("hero".to_string(), Json::U64(self.hero_id as u64));
+        obj.insert("goal".to_string(), self.goal.as_ref().map_or(Json::Null, pos_to_json));
+        obj.insert("reason".to_string(), Json::String(self.reason.clone()));
+        obj.insert("candidates".to_string(), Json::Array(self.candidates.iter().map(|candidate| {
+            let mut entry: json::Object = json::Object::new();
+            entry.insert("pos".to_string(), pos_to_json(&candidate.pos));
+            entry.insert("score".to_string(), Json::F64(candidate.score as f64));
+            Json::Object(entry)
+        }).collect()));
+        obj.insert("path_length".to_string(), self.path_length.map_or(Json::Null, |steps| Json::U64(steps as u64)));
+        obj.insert("dir".to_string(), Json::String(self.dir.to_string()));
+        obj.insert("vetoed".to_string(), self.vetoed.as_ref().map_or(Json::Null, |dir| Json::String(dir.to_string())));
+        obj.insert("millis".to_string(), Json::F64(self.millis as f64));
+        Json::Object(obj)
+    }
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut text = format!("Turn {}: {}", self.turn, self.dir);
+        match self.goal {
+            Some(ref goal) => text.push_str(&format!(" toward ({}, {})", goal.x, goal.y)),
+            None => {}
+        }
+        if !self.reason.is_empty() {
+            text.push_str(&format!(", {}", self.reason));
+        }
+        match self.vetoed {
+            Some(ref vetoed) => text.push_str(&format!(", vetoed {} as fatal", vetoed)),
+            None => {}
+        }
+        match self.path_length {
+            Some(steps) => text.push_str(&format!(" [{} steps, ", steps)),
+            None => text.push_str(" [")
+        }
+        write!(f, "{}{} candidates, {:.1} ms]", text, self.candidates.len(), self.millis)
+    }
+}
+
+/// Writes one JSON object per line, one line per decision
+pub fn save(filename: &str, decisions: &[Decision]) -> Result<(), String> {
+    let text = decisions.iter().map(|decision| format!("{}\n", decision.to_json())).collect::<String>();
+    File::create(&Path::new(filename)).and_then(|mut file| file.write_all(text.as_bytes()))
+                                      .map_err(|err| err.to_string())
+}
@@ -0,0 +1,222 @@
+extern crate rand;
+extern crate rustc_serialize;
+use self::rand::{SeedableRng, StdRng};
+use self::rand::distributions::{Normal, IndependentSample};
+use self::rustc_serialize::json;
+use self::rustc_serialize::json::Json;
+use std::fs;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use bot::Bot;
+use params::Params;
+use evaluator::Evaluator;
+use simulator::HERO_COUNT;
+use tuning;
+
+#[derive(Debug, Clone)]
+pub struct TrainConfig {
+    pub iterations: usize,
+    pub games: usize, // games per version and iteration
+    pub turns: isize, // game turns per game
+    pub mutation: f32, // mutation spread, as a share of each parameter's range and in evaluator weight units
+    pub pool: usize, // most recent checkpoints kept around as opponents
+    pub seed: usize,
+    pub dir: String // where checkpoints are written
+}
+
+/// Everything that makes one version of our bot play the way it does
+#[derive(Debug, Clone, PartialEq)]
+pub struct Version {
+    pub params: Params,
+    pub evaluator: Option<Evaluator>
+}
+
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    pub number: usize,
+    pub version: Version,
+    pub score: f32 // average score against the opponent pool when it was saved
+}
+
+impl TrainConfig {
+    pub fn new() -> TrainConfig {
+        TrainConfig {
+            iterations: 100,
+            games: 4,
+            turns: 1200,
+            mutation: 0.1,
+            pool: 5,
+            seed: 1,
+            dir: "checkpoints".to_string()
+        }
+    }
+}
+
+fn perturb(rng: &mut StdRng, weights: &[f32], spread: f32) -> Vec<f32> {
+    let normal = Normal::new(0.0, spread.max(::std::f32::EPSILON) as f64);
+    weights.iter().map(|w| w + normal.ind_sample(rng) as f32).collect()
+}
+
+impl Version {
+    pub fn new() -> Version {
+        Version {params: Params::new(), evaluator: None}
+    }
+
+    pub fn bot(&self) -> Bot {
+        let mut bot = Bot::with_params(self.params.clone());
+        match self.evaluator {
+            Some(ref evaluator) => bot.set_evaluator(evaluator.clone()),
+            None => {}
+        }
+        bot
+    }
+
+    /// A copy with its parameters and evaluator weights nudged at random
+    pub fn mutate(&self, rng: &mut StdRng, mutation: f32) -> Version {
+        let evaluator = match self.evaluator {
+            Some(Evaluator::Linear(ref weights)) => Some(Evaluator::Linear(perturb(rng, weights, mutation))),
+            Some(Evaluator::Mlp {ref hidden, ref output}) => Some(Evaluator::Mlp {
+                hidden: hidden.iter().map(|row| perturb(rng, row, mutation)).collect(),
+                output: perturb(rng, output, mutation)
+            }),
+            None => None
+        };
+        Version {params: tuning::mutate(rng, &self.params, mutation), evaluator: evaluator}
+    }
+}
+
+impl Checkpoint {
+    pub fn filename(dir: &str, number: usize) -> String {
+        format!("{}/checkpoint-{:04}.json", dir, number)
+    }
+
+    pub fn save(&self, dir: &str) -> Result<String, String> {
+        let mut obj: json::Object = json::Object::new();
+        obj.insert("number".to_string(), Json::U64(self.number as u64));
+        obj.insert("score".to_string(), Json::F64(self.score as f64));
+        obj.insert("params".to_string(), self.version.params.to_json());
+        obj.insert("evaluator".to_string(), match self.version.evaluator {
+            Some(ref evaluator) => evaluator.to_json(),
+            None => Json::Null
+        });
+        let filename = Checkpoint::filename(dir, self.number);
+        let text = format!("{}\n", Json::Object(obj).pretty());
+        fs::create_dir_all(dir).and_then(|_| File::create(&Path::new(&filename)))
+                               .and_then(|mut file| file.write_all(text.as_bytes()))
+                               .map(|_| filename)
+                               .map_err(|err| err.to_string())
+    }
+
+    pub fn load(filename: &str) -> Option<Checkpoint> {
+        let mut json_str = String::new();
+        match File::open(&Path::new(filename)).and_then(|mut file| file.read_to_string(&mut json_str)) {
+            Ok(_) => {},
+            Err(_) => return None
+        }
+        let obj = match Json::from_str(&json_str) {
+            Ok(Json::Object(obj)) => obj,
+            _ => return None
+        };
+        let params = match obj.get("params").and_then(Params::from_json) {
+            Some(params) => params,
+            None => return None
+        };
+        Some(Checkpoint {
+            number: obj.get("number").and_then(|n| n.as_u64()).unwrap_or(0) as usize,
+            version: Version {params: params, evaluator: obj.get("evaluator").and_then(Evaluator::from_json)},
+            score: obj.get("score").and_then(|s| s.as_f64()).unwrap_or(0.0) as f32
+        })
+    }
+
+    /// Every checkpoint saved in `dir`, oldest first
+    pub fn list(dir: &str) -> Vec<Checkpoint> {
+        let mut checkpoints = match fs::read_dir(dir) {
+            Ok(entries) => entries.filter_map(|entry| entry.ok())
+                                  .filter_map(|entry| entry.path().to_str().and_then(Checkpoint::load))
+                                  .collect::<Vec<Checkpoint>>(),
+            Err(_) => vec!()
+        };
+        checkpoints.sort_by_key(|checkpoint| checkpoint.number);
+        checkpoints
+    }
+}
+
+/// Average score of `version` over one game per seed, with frozen versions from `pool`
+/// taking the other seats in turn
+pub fn evaluate(version: &Version, pool: &[Version], seeds: &[usize], turns: isize) -> f32 {
+    let mut total = 0.0;
+    for (game, seed) in seeds.iter().enumerate() {
+        let seat = game % HERO_COUNT;
+        let mut players = (0..HERO_COUNT).map(|i| {
+            if i == seat { version.bot() } else { pool[(game + i) % pool.len()].bot() }
+        }).collect::<Vec<Bot>>();
+        total += tuning::score_seat(&mut players, seat, *seed, turns);
+    }
+    total / seeds.len().max(1) as f32
+}
+
+/// Self-play hill climbing. Each iteration a mutated copy of the current version plays the
+/// same games as the current version against earlier checkpoints, and replaces it if it
+/// scores at least as well. Picks up after the last checkpoint in `config.dir`, if any,
+/// and saves a numbered checkpoint after every iteration. Resuming a run that started
+/// from another version than `start` is an error rather than a silent switch.
+/// `progress` hears each new checkpoint with the scores of the candidate and the version it challenged.
+pub fn train<F>(config: &TrainConfig, start: Version, mut progress: F) -> Result<Vec<Checkpoint>, String>
+    where F: FnMut(&Checkpoint, f32, f32) {
+    let mut saved = Checkpoint::list(&config.dir);
+    match saved.first() {
+        Some(first) if first.version != start => {
+            return Err(format!("The checkpoints in {} start from another version, \
+                                use a new directory or the same starting version", config.dir))
+        },
+        _ => {}
+    }
+    let mut pool = saved.iter().rev().take(config.pool).rev()
+                        .map(|checkpoint| checkpoint.version.clone())
+                        .collect::<Vec<Version>>();
+    let (mut current, mut number) = match saved.last() {
+        Some(last) => (last.version.clone(), last.number),
+        None => {
+            pool.push(start.clone());
+            let seeds = (0..config.games).map(|game| config.seed * 1000 + game).collect::<Vec<usize>>();
+            let score = evaluate(&start, &pool, &seeds, config.turns);
+            let first = Checkpoint {number: 0, version: start.clone(), score: score};
+            match first.save(&config.dir) {
+                Ok(_) => saved.push(first),
+                Err(err) => return Err(err)
+            }
+            (start, 0)
+        }
+    };
+
+    let mut rng: StdRng = SeedableRng::from_seed(&[config.seed, number][..]);
+    for _ in 0..config.iterations {
+        number += 1;
+        let seeds = (0..config.games).map(|game| {
+            config.seed * 1000 + number * config.games + game
+        }).collect::<Vec<usize>>();
+        let candidate = current.mutate(&mut rng, config.mutation);
+        let candidate_score = evaluate(&candidate, &pool, &seeds, config.turns);
+        let current_score = evaluate(&current, &pool, &seeds, config.turns);
+        let score = if candidate_score >= current_score {
+            current = candidate;
+            candidate_score
+        } else {
+            current_score
+        };
+
+        let checkpoint = Checkpoint {number: number, version: current.clone(), score: score};
+        match checkpoint.save(&config.dir) {
+            Ok(_) => progress(&checkpoint, candidate_score, current_score),
+            Err(err) => return Err(err)
+        }
+        saved.push(checkpoint);
+
+        pool.push(current.clone());
+        while pool.len() > config.pool {
+            pool.remove(0);
+        }
+    }
+    Ok(saved)
+}
@@ -0,0 +1,126 @@
+extern crate rand;
+use self::rand::{Rng, SeedableRng, StdRng};
+use self::rand::distributions::{Normal, IndependentSample};
+use std::cmp::Ordering;
+use bot::Bot;
+use params;
+use params::Params;
+use simulator;
+use simulator::{Simulator, HERO_COUNT};
+
+const ELITE: usize = 2; // best sets copied unchanged into the next generation
+const TOURNAMENT: usize = 3; // contestants per parent pick
+const GOLD_SCALE: f32 = 1000.0; // gold worth one place in the final ranking
+
+#[derive(Debug, Clone)]
+pub struct TuneConfig {
+    pub population: usize,
+    pub generations: usize,
+    pub games: usize, // games per parameter set and generation
+    pub turns: isize, // game turns per game
+    pub mutation: f32, // mutation spread, as a share of each parameter's range
+    pub seed: usize
+}
+
+#[derive(Debug, Clone)]
+pub struct Scored {
+    pub params: Params,
+    pub fitness: f32
+}
+
+impl TuneConfig {
+    pub fn new() -> TuneConfig {
+        TuneConfig {population: 12, generations: 10, games: 4, turns: 1200, mutation: 0.1, seed: 1}
+    }
+}
+
+/// Plays one game on the map of `seed` with `players[i]` controlling hero `i + 1`, and scores
+/// hero `seat + 1`. Each place in the final ranking is worth a point, plus a point per `GOLD_SCALE` gold.
+pub fn score_seat(players: &mut [Bot], seat: usize, seed: usize, turns: isize) -> f32 {
+    let mut sim = Simulator::new(seed);
+    sim.game.max_turns = turns;
+    let heroes = sim.run(players);
+    let hero = &heroes[seat];
+    let rank = simulator::rank(&heroes, hero.id);
+    (heroes.len() - rank) as f32 + hero.gold as f32 / GOLD_SCALE
+}
+
+/// Average score of `candidate` over one game per seed, against bots running the defaults
+pub fn fitness(candidate: &Params, seeds: &[usize], turns: isize) -> f32 {
+    let mut total = 0.0;
+    for (game, seed) in seeds.iter().enumerate() {
+        // rotate through the seats, spawns are symmetric but turn order is not
+        let seat = game % HERO_COUNT;
+        let mut players = (0..HERO_COUNT).map(|i| {
+            if i == seat { Bot::with_params(candidate.clone()) } else { Bot::new() }
+        }).collect::<Vec<Bot>>();
+        total += score_seat(&mut players, seat, *seed, turns);
+    }
+    total / seeds.len().max(1) as f32
+}
+
+fn random_params(rng: &mut StdRng) -> Params {
+    let values = params::BOUNDS.iter().map(|&(low, high)| rng.gen_range(low, high)).collect::<Vec<f32>>();
+    Params::from_vec(&values)
+}
+
+fn pick<'a>(rng: &mut StdRng, scored: &'a [Scored]) -> &'a Scored {
+    (0..TOURNAMENT).map(|_| &scored[rng.gen_range(0, scored.len())])
+                   .max_by(|a, b| a.fitness.partial_cmp(&b.fitness).unwrap_or(Ordering::Equal))
+                   .unwrap()
+}
+
+/// A copy of `params` with every parameter nudged at random
+pub fn mutate(rng: &mut StdRng, params: &Params, mutation: f32) -> Params {
+    breed(rng, params, params, mutation)
+}
+
+/// Uniform crossover of two parents followed by gaussian mutation of every parameter
+fn breed(rng: &mut StdRng, a: &Params, b: &Params, mutation: f32) -> Params {
+    let (a, b) = (a.to_vec(), b.to_vec());
+    let values = params::BOUNDS.iter().enumerate().map(|(i, &(low, high))| {
+        let gene = if rng.gen::<bool>() { a[i] } else { b[i] };
+        let spread = ((high - low) * mutation).max(::std::f32::EPSILON) as f64;
+        gene + Normal::new(0.0, spread).ind_sample(rng) as f32
+    }).collect::<Vec<f32>>();
+    Params::from_vec(&values)
+}
+
+/// Evolves parameter sets over simulated games and returns the best one seen.
+/// Every set of a generation plays the same maps, so their scores compare fairly.
+/// `progress` hears the number and best set of each generation as it finishes.
+pub fn evolve<F: FnMut(usize, &Scored)>(config: &TuneConfig, mut progress: F) -> Scored {
+    let mut rng: StdRng = SeedableRng::from_seed(&[config.seed][..]);
+    let mut population = vec!(Params::new());
+    while population.len() < config.population {
+        population.push(random_params(&mut rng));
+    }
+
+    let mut best = Scored {params: Params::new(), fitness: ::std::f32::MIN};
+    for generation in 0..config.generations {
+        let seeds = (0..config.games).map(|game| {
+            config.seed * 1000 + generation * config.games + game
+        }).collect::<Vec<usize>>();
+        let mut scored = population.iter().map(|candidate| Scored {
+            params: candidate.clone(),
+            fitness: fitness(candidate, &seeds, config.turns)
+        }).collect::<Vec<Scored>>();
+        scored.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap_or(Ordering::Equal));
+
+        if scored[0].fitness > best.fitness {
+            best = scored[0].clone();
+        }
+        progress(generation, &scored[0]);
+
+        population = scored.iter().take(ELITE).map(|s| s.params.clone()).collect();
+        while population.len() < config.population {
+            let child = {
+                let a = pick(&mut rng, &scored);
+                let b = pick(&mut rng, &scored);
+                breed(&mut rng, &a.params, &b.params, config.mutation)
+            };
+            population.push(child);
+        }
+    }
+    best
+}
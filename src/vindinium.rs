@@ -89,7 +89,7 @@ pub enum Tile {
     Mine(Option<HeroId>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Dir {
     Stay,
     North,
@@ -315,6 +315,19 @@ impl Decodable for State {
 
 // Misc.
 
+impl Dir {
+    /// Change in (x, y) when moving in this direction, where x is the row
+    pub fn offset(&self) -> (isize, isize) {
+        match *self {
+            Dir::Stay => (0, 0),
+            Dir::North => (-1, 0),
+            Dir::South => (1, 0),
+            Dir::East => (0, 1),
+            Dir::West => (0, -1),
+        }
+    }
+}
+
 impl State {
     pub fn clear_pretty_print(&self) {
         // clear game info
@@ -0,0 +1,128 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::vindinium::{State, Tile, Pos, Dir};
+use vindinium_bot::pathing::UVector2;
+use vindinium_bot::bot::Bot;
+use vindinium_bot::params::Params;
+use vindinium_bot::evaluator::Evaluator;
+use common::{load_state, place_hero};
+
+#[test]
+fn crashed_leaders_are_not_gambled_on() {
+    let mut state = load_state();
+    state.hero.life = 100;
+    state.game.heroes[3].life = 100;
+    // hero 1 is crashed next to us and holds the lead we need
+    state.game.heroes[0].gold = 5000;
+    state.game.heroes[0].life = 10;
+    state.game.heroes[0].mine_count = 2;
+
+    let mut bot = Bot::new();
+    bot.step(&state);
+    let decision = bot.last_decision().unwrap();
+    assert!(decision.goal != Some(UVector2 {x: 5, y: 6}));
+    assert!(!decision.reason.starts_with("gambling"));
+    assert!(decision.path_length.is_some());
+}
+
+/// Hero 3 as the rich but weak leader, standing on `pos`
+fn weak_leader_at(x: usize, y: usize) -> State {
+    let mut state = load_state();
+    state.hero.life = 100;
+    state.game.heroes[3].life = 100;
+    let leader = place_hero(&mut state, 3, Pos {x: x as isize, y: y as isize});
+    leader.gold = 5000;
+    leader.life = 10;
+    leader.mine_count = 2;
+    state
+}
+
+#[test]
+fn gambles_only_on_leaders_within_reach() {
+    let mut bot = Bot::new();
+    bot.step(&weak_leader_at(6, 9));
+    assert_eq!(bot.last_decision().unwrap().reason, "gambling on enemy 3 for the lead");
+
+    let mut bot = Bot::new();
+    bot.step(&weak_leader_at(17, 10));
+    assert!(!bot.last_decision().unwrap().reason.starts_with("gambling"));
+}
+
+#[test]
+fn fights_the_enemy_that_can_reach_us_first() {
+    let mut state = load_state();
+    state.game.turn = 100;
+    state.hero.life = 30;
+    state.game.heroes[3].life = 30;
+    // hero 1 is weak and close as the crow flies, but the walls put it eight steps away
+    state.game.board.tiles[3][7] = Tile::Mine(Some(1));
+    {
+        let weak = &mut state.game.heroes[0];
+        weak.crashed = false;
+        weak.life = 10;
+        weak.mine_count = 1;
+    }
+    // hero 2 is strong and four steps away
+    place_hero(&mut state, 2, Pos {x: 6, y: 10}).life = 100;
+
+    let mut bot = Bot::new();
+    bot.step(&state);
+    assert!(bot.last_decision().unwrap().reason != "attacking enemy 1");
+}
+
+/// Hero 2 camping our spawn with more mines than us, while we are one blow from death
+/// next to a free mine
+fn spawn_camped() -> State {
+    let mut state = load_state();
+    state.game.turn = 100;
+    state.hero.life = 15;
+    state.game.heroes[3].life = 15;
+    state.game.board.tiles[3][8] = Tile::Mine(None);
+    place_hero(&mut state, 2, Pos {x: 5, y: 11}).mine_count = 8;
+    state
+}
+
+#[test]
+fn dying_on_purpose_is_off_by_default() {
+    let mut bot = Bot::new();
+    assert!(!bot.params().exploit_telefrag);
+    bot.step(&spawn_camped());
+    assert!(!bot.last_decision().unwrap().reason.starts_with("dying"));
+
+    let mut params = Params::new();
+    params.exploit_telefrag = true;
+    let mut bot = Bot::with_params(params);
+    bot.step(&spawn_camped());
+    let decision = bot.last_decision().unwrap();
+    assert_eq!(decision.reason, "dying to telefrag enemy 2");
+    assert_eq!(decision.goal, Some(UVector2 {x: 3, y: 8}));
+}
+
+#[test]
+fn evaluator_moves_are_checked_for_stalls() {
+    // the same board every turn means every move came to nothing
+    let state = load_state();
+    let mut bot = Bot::new();
+    bot.set_evaluator(Evaluator::new());
+    let first = bot.step(&state);
+    for _ in 0..4 {
+        bot.step(&state);
+    }
+    let decision = bot.last_decision().unwrap();
+    assert!(decision.reason.contains("after getting stuck Stalled"));
+    assert!(decision.dir != first);
+}
+
+#[test]
+fn goals_behind_a_fatal_step_are_given_up() {
+    // the way to the closest tavern passes a stronger enemy
+    let mut state = load_state();
+    state.hero.life = 60;
+    place_hero(&mut state, 1, Pos {x: 5, y: 7}).life = 100;
+    let mut bot = Bot::new();
+    let dir = bot.step(&state);
+    let decision = bot.last_decision().unwrap();
+    assert_eq!(decision.vetoed, Some(Dir::South));
+    assert!(dir != Dir::Stay && dir != Dir::South);
+    assert!(decision.goal != Some(UVector2 {x: 6, y: 6}));
+}
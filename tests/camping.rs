@@ -0,0 +1,49 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::vindinium::{State, Tile};
+use vindinium_bot::pathing::{UVector2};
+use vindinium_bot::opponent::{OpponentModel};
+use vindinium_bot::camping;
+use common::load_state;
+
+// Hero 1 parked right above the tavern at (6,6)
+fn with_camper(mut state: State) -> State {
+    let (x, y) = (state.game.heroes[0].pos.x as usize, state.game.heroes[0].pos.y as usize);
+    state.game.board.tiles[x][y] = Tile::Free;
+    state.game.board.tiles[5][6] = Tile::Hero(1);
+    state.game.heroes[0].pos.x = 5;
+    state.game.heroes[0].pos.y = 6;
+    state.game.heroes[0].crashed = false;
+    state
+}
+
+#[test]
+fn crashed_heroes_are_not_campers() {
+    let state = load_state();
+    let mut model = OpponentModel::new();
+    for _ in 0..8 {
+        model.observe(&state);
+    }
+    assert!(camping::detect(&state, &model).is_empty());
+}
+
+#[test]
+fn hero_staying_by_a_tavern_is_camping() {
+    let state = with_camper(load_state());
+    let mut model = OpponentModel::new();
+    for _ in 0..3 {
+        model.observe(&state);
+    }
+    assert!(camping::detect(&state, &model).is_empty());
+    for _ in 0..5 {
+        model.observe(&state);
+    }
+    let camps = camping::detect(&state, &model);
+    assert_eq!(camps.len(), 1);
+    assert_eq!(camps[0].hero_id, 1);
+    assert_eq!(camps[0].tavern, UVector2 {x: 6, y: 6});
+    assert_eq!(camping::at_tavern(&state, &UVector2 {x: 5, y: 6}), Some(UVector2 {x: 6, y: 6}));
+
+    // our hero is at 38 life, so the healed camper wins
+    assert_eq!(camping::dangerous_taverns(&state, &camps), vec!(UVector2 {x: 6, y: 6}));
+}
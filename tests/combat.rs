@@ -0,0 +1,57 @@
+extern crate vindinium_bot;
+use vindinium_bot::vindinium::{Hero, Pos};
+use vindinium_bot::combat;
+
+fn hero(id: isize, x: isize, y: isize, life: isize, mine_count: isize) -> Hero {
+    Hero {
+        id: id,
+        name: format!("hero{}", id),
+        user_id: None,
+        elo: None,
+        pos: Pos {x: x, y: y},
+        life: life,
+        gold: 0,
+        mine_count: mine_count,
+        spawn_pos: Pos {x: x, y: y},
+        crashed: false,
+    }
+}
+
+#[test]
+fn first_strike_wins_even_fight() {
+    let a = hero(1, 0, 0, 50, 2);
+    let b = hero(2, 0, 1, 50, 3);
+
+    let prediction = combat::duel(&a, &b);
+    assert_eq!(prediction.winner(), Some(1));
+    assert_eq!(prediction.exchanges, 5);
+    assert_eq!(prediction.mines_of(1), 5);
+
+    let prediction = combat::duel(&b, &a);
+    assert_eq!(prediction.winner(), Some(2));
+}
+
+#[test]
+fn lower_life_can_win_by_moving_first() {
+    let a = hero(1, 0, 0, 45, 0);
+    let b = hero(2, 0, 1, 60, 1);
+    let order = combat::turn_order(&[a.clone(), b.clone()], 0);
+    assert_eq!(order, vec!(1, 2));
+
+    let prediction = combat::predict(&[&a, &b], &order);
+    assert!(prediction.survives(1));
+    assert!(!prediction.survives(2));
+}
+
+#[test]
+fn brawl_ignores_heroes_out_of_reach() {
+    let a = hero(1, 0, 0, 100, 0);
+    let b = hero(2, 0, 1, 20, 4);
+    let c = hero(3, 5, 5, 10, 1);
+
+    let prediction = combat::predict(&[&a, &b, &c], &[2, 3, 1]);
+    assert_eq!(prediction.deaths.len(), 1);
+    assert_eq!(prediction.deaths[0].killer_id, 1);
+    assert_eq!(prediction.mines_of(1), 4);
+    assert!(prediction.survives(3));
+}
@@ -0,0 +1,34 @@
+// Helpers shared by the integration tests; not every test file uses all of them.
+#![allow(dead_code)]
+extern crate vindinium_bot;
+extern crate rustc_serialize;
+use std::string::String;
+use std::fs::File;
+use std::path::Path;
+use std::io::Read;
+use self::vindinium_bot::vindinium::{State, Hero, HeroId, Pos, Tile};
+use self::rustc_serialize::json;
+
+pub fn load_state() -> State {
+    let mut json_str = String::new();
+    let res = File::open(&Path::new("tests/test_state.json")).unwrap().read_to_string(&mut json_str);
+    match res {
+        Ok(_) => {}
+        Err(err) => panic!("{}", err)
+    }
+
+    match json::decode(&json_str) {
+        Ok(state) => state,
+        Err(err) => panic!("{}", err)
+    }
+}
+
+/// Moves hero `id` onto `pos`, clearing the tile it stood on, and brings it back if it had crashed
+pub fn place_hero(state: &mut State, id: HeroId, pos: Pos) -> &mut Hero {
+    let hero = state.game.heroes.iter_mut().find(|hero| hero.id == id).unwrap();
+    state.game.board.tiles[hero.pos.x as usize][hero.pos.y as usize] = Tile::Free;
+    state.game.board.tiles[pos.x as usize][pos.y as usize] = Tile::Hero(id);
+    hero.pos = pos;
+    hero.crashed = false;
+    hero
+}
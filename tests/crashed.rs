@@ -0,0 +1,38 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::vindinium::{State, Tile};
+use vindinium_bot::pathing;
+use vindinium_bot::pathing::{UVector2, Map};
+use vindinium_bot::mines;
+use vindinium_bot::mines::MineLedger;
+use vindinium_bot::crashed;
+use common::load_state;
+
+fn farm(state: &State) -> Vec<mines::MineValue> {
+    let map = Map::from(&state.game.board);
+    let distances = pathing::distance_map(&UVector2::from(&state.hero.pos), &map);
+    let values = mines::evaluate(state, &MineLedger::new(), &distances);
+    crashed::farm(state, &values)
+}
+
+#[test]
+fn mines_of_crashed_heroes_are_farmed() {
+    let mut state = load_state();
+    assert_eq!(crashed::crashed_heroes(&state), vec!(1, 2, 3));
+    assert!(farm(&state).is_empty());
+
+    state.game.board.tiles[3][7] = Tile::Mine(Some(2));
+    state.hero.life = 100;
+    let farm = farm(&state);
+    assert_eq!(farm.len(), 1);
+    assert_eq!(farm[0].pos, UVector2 {x: 3, y: 7});
+}
+
+#[test]
+fn mines_of_active_heroes_are_not_farmed() {
+    let mut state = load_state();
+    state.game.board.tiles[3][7] = Tile::Mine(Some(2));
+    state.game.heroes[1].crashed = false;
+    state.hero.life = 100;
+    assert!(farm(&state).is_empty());
+}
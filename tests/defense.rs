@@ -0,0 +1,72 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::vindinium::{Tile};
+use vindinium_bot::pathing;
+use vindinium_bot::pathing::{UVector2, Map};
+use vindinium_bot::mines;
+use vindinium_bot::mines::MineLedger;
+use vindinium_bot::defense;
+use common::load_state;
+
+#[test]
+fn ledger_remembers_who_took_our_mine() {
+    let mut state = load_state();
+    let mut ledger = MineLedger::new();
+    ledger.observe(&state);
+    assert!(ledger.stolen().is_empty());
+
+    let mine = UVector2 {x: 3, y: 7};
+    state.game.board.tiles[mine.x][mine.y] = Tile::Mine(Some(1));
+    ledger.observe(&state);
+    assert_eq!(ledger.get(&mine).unwrap().lost_to, Some(1));
+    assert_eq!(ledger.stolen().len(), 1);
+
+    state.game.board.tiles[mine.x][mine.y] = Tile::Mine(Some(4));
+    ledger.observe(&state);
+    assert!(ledger.stolen().is_empty());
+}
+
+#[test]
+fn mine_stolen_by_a_crashed_hero_is_recaptured() {
+    let mut state = load_state();
+    let mut ledger = MineLedger::new();
+    ledger.observe(&state);
+    state.game.board.tiles[3][7] = Tile::Mine(Some(1));
+    ledger.observe(&state);
+
+    let map = Map::from(&state.game.board);
+    let distances = pathing::distance_map(&UVector2::from(&state.hero.pos), &map);
+    let values = mines::evaluate(&state, &ledger, &distances);
+    let recaptures = defense::recaptures(&state, &ledger, &map, &values);
+    assert_eq!(recaptures.len(), 1);
+    assert_eq!(recaptures[0].pos, UVector2 {x: 3, y: 7});
+    assert_eq!(recaptures[0].thief, 1);
+    assert!(recaptures[0].thief_weak);
+    assert_eq!(recaptures[0].thief_steps, None);
+}
+
+#[test]
+fn weak_raider_near_our_cluster_is_guarded_against() {
+    let mut state = load_state();
+    let map = Map::from(&state.game.board);
+    assert!(defense::guard(&state, &map).is_none());
+
+    let (x, y) = (state.game.heroes[0].pos.x as usize, state.game.heroes[0].pos.y as usize);
+    state.game.board.tiles[x][y] = Tile::Free;
+    state.game.board.tiles[12][8] = Tile::Hero(1);
+    state.game.heroes[0].pos.x = 12;
+    state.game.heroes[0].pos.y = 8;
+    state.game.heroes[0].crashed = false;
+    state.game.heroes[0].life = 10;
+    state.hero.life = 100;
+
+    let map = Map::from(&state.game.board);
+    let guard = defense::guard(&state, &map).unwrap();
+    assert_eq!(guard.threat, 1);
+    assert_eq!(guard.mines.len(), 4);
+    assert_eq!(guard.pos, UVector2 {x: 7, y: 7});
+
+    state.game.heroes[0].life = 100;
+    state.hero.life = 10;
+    assert!(defense::guard(&state, &map).is_none());
+}
@@ -0,0 +1,37 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::opponent::{OpponentModel, Behavior};
+use vindinium_bot::elo;
+use common::load_state;
+
+#[test]
+fn stronger_enemies_call_for_caution() {
+    let mut state = load_state();
+    assert!((elo::expected_score(1200, 1200) - 0.5).abs() < 1e-6);
+    assert!((elo::caution(&state, &state.game.heroes[0]) - 1.0).abs() < 1e-6);
+    assert_eq!(elo::judged(&state, &state.game.heroes[0]).life, state.hero.life);
+
+    state.game.heroes[0].elo = Some(1800);
+    state.game.heroes[1].elo = Some(800);
+    assert!(elo::caution(&state, &state.game.heroes[0]) > 1.0);
+    assert!(elo::caution(&state, &state.game.heroes[1]) < 1.0);
+    assert!(elo::judged(&state, &state.game.heroes[0]).life < state.hero.life);
+    assert!(elo::judged(&state, &state.game.heroes[1]).life > state.hero.life);
+}
+
+#[test]
+fn elo_shapes_the_behavior_prior() {
+    let mut state = load_state();
+    state.game.heroes[0].elo = Some(1800);
+    state.game.heroes[1].elo = Some(800);
+    state.game.heroes[2].elo = None;
+    let mut model = OpponentModel::new();
+    model.observe(&state);
+
+    let strong = model.profile(1).unwrap();
+    assert!(strong.probability(&Behavior::TavernCamper) > strong.probability(&Behavior::Random));
+    let weak = model.profile(2).unwrap();
+    assert_eq!(weak.behavior(), Behavior::Random);
+    let unrated = model.profile(3).unwrap();
+    assert!((unrated.probability(&Behavior::Random) - 0.2).abs() < 1e-6);
+}
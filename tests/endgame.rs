@@ -0,0 +1,39 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::endgame;
+use vindinium_bot::endgame::Mode;
+use common::load_state;
+
+#[test]
+fn turns_left_follow_turn_order() {
+    let state = load_state();
+    assert_eq!(endgame::turns_left(&state, 1), 25);
+    assert_eq!(endgame::turns_left(&state, 4), 25);
+
+    let mut late = state.clone();
+    late.game.turn = 1198;
+    assert_eq!(endgame::turns_left(&late, 3), 1);
+    assert_eq!(endgame::turns_left(&late, 4), 1);
+    assert_eq!(endgame::turns_left(&late, 1), 0);
+}
+
+#[test]
+fn leader_protects_lead() {
+    let state = load_state();
+    let projections = endgame::project(&state);
+    assert_eq!(projections[0].hero_id, 4);
+    assert_eq!(projections[0].gold, 1078 + 6 * 25);
+    assert_eq!(endgame::rank(&state), 1);
+    assert_eq!(endgame::mode(&state), Mode::ProtectLead);
+}
+
+#[test]
+fn trailing_hero_gambles() {
+    let mut state = load_state();
+    state.hero = state.game.heroes[0].clone();
+    assert_eq!(endgame::rank(&state), 2);
+    assert_eq!(endgame::mode(&state), Mode::Gamble);
+
+    state.game.turn = 100;
+    assert_eq!(endgame::mode(&state), Mode::Normal);
+}
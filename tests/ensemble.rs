@@ -0,0 +1,130 @@
+extern crate vindinium_bot;
+extern crate rustc_serialize;
+mod common;
+use std::cell::RefCell;
+use std::rc::Rc;
+use vindinium_bot::vindinium::{State, Dir};
+use vindinium_bot::bot::Bot;
+use vindinium_bot::features;
+use vindinium_bot::ensemble;
+use vindinium_bot::ensemble::{Ensemble, EnsembleConfig, Arbitration, Gate, Strategy, Proposal, Combat, Shared};
+use vindinium_bot::simulator::Simulator;
+use common::load_state;
+
+struct Fixed {
+    name: String,
+    dir: Dir,
+    confidence: f32
+}
+
+impl Strategy for Fixed {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn propose(&mut self, _: &State) -> Option<Proposal> {
+        Some(Proposal {dir: self.dir.clone(), confidence: self.confidence})
+    }
+}
+
+fn ensemble(arbitration: Arbitration) -> Ensemble {
+    let mut ensemble = Ensemble::new(arbitration);
+    ensemble.register(Box::new(Fixed {name: "combat".to_string(), dir: Dir::North, confidence: 0.9}), 1.0);
+    ensemble.register(Box::new(Fixed {name: "miner".to_string(), dir: Dir::South, confidence: 0.6}), 1.0);
+    ensemble.register(Box::new(Fixed {name: "other".to_string(), dir: Dir::South, confidence: 0.6}), 1.0);
+    ensemble
+}
+
+#[test]
+fn arbitration_modes_pick_different_moves() {
+    let mut state = load_state();
+    state.hero.life = 100;
+
+    // two lukewarm votes outweigh one confident one
+    let mut voting = ensemble(Arbitration::WeightedVote);
+    assert_eq!(voting.step(&state), Dir::South);
+    assert_eq!(voting.proposals().len(), 3);
+    assert_eq!(voting.proposals()[0].0, "combat");
+
+    // unless the confident one has priority
+    let mut priority = ensemble(Arbitration::Priority(0.8));
+    assert_eq!(priority.step(&state), Dir::North);
+    priority.set_arbitration(Arbitration::Priority(0.95));
+    assert_eq!(priority.step(&state), Dir::South);
+}
+
+#[test]
+fn gate_learns_whom_to_trust() {
+    let mut state = load_state();
+    state.hero.life = 100;
+    let inputs = vec![1.0; features::FEATURE_COUNT];
+    let mut gate = Gate::new(3);
+    assert_eq!(gate.trust(&inputs), vec!(1.0 / 3.0; 3));
+    for _ in 0..20 {
+        gate.learn(&inputs, 0, 0.1);
+    }
+    let trust = gate.trust(&inputs);
+    assert!(trust[0] > 0.9);
+    assert!((trust.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+    assert_eq!(Gate::from_json(&gate.to_json()), Some(gate.clone()));
+
+    // a gate that only weighs the bias feature trusts the first strategy in every situation
+    let mut bias = Gate::new(3);
+    bias.weights[0][0] = 5.0;
+    let mut gated = ensemble(Arbitration::Gated(bias));
+    assert_eq!(gated.step(&state), Dir::North);
+}
+
+#[test]
+fn combat_layered_over_the_bot_plays_whole_games() {
+    let mut sim = Simulator::new(5);
+    sim.game.max_turns = 200;
+    let mut players = (0..4).map(|_| {
+        let mut ensemble = Ensemble::new(Arbitration::Priority(0.8));
+        ensemble.register(Box::new(Combat), 1.0);
+        ensemble.register(Box::new(Bot::new()), 1.0);
+        ensemble
+    }).collect::<Vec<Ensemble>>();
+    let heroes = sim.run(&mut players);
+    assert_eq!(heroes.len(), 4);
+    assert!(heroes.iter().any(|hero| hero.gold > 0));
+}
+
+#[test]
+fn the_bot_only_records_the_move_that_was_made() {
+    let mut state = load_state();
+    state.hero.life = 100;
+    let bot = Rc::new(RefCell::new(Bot::new()));
+    let mut ensemble = Ensemble::new(Arbitration::Priority(0.8));
+    ensemble.register(Box::new(Fixed {name: "combat".to_string(), dir: Dir::North, confidence: 0.9}), 1.0);
+    ensemble.register(Box::new(Shared::new(&bot)), 1.0);
+
+    assert_eq!(ensemble.step(&state), Dir::North);
+    assert_eq!(ensemble.proposals()[1].0, "bot");
+    let bot = bot.borrow();
+    assert_eq!(bot.trace().len(), 1);
+    assert_eq!(bot.last_decision().unwrap().dir, Dir::North);
+    assert!(ensemble.proposals()[1].1.dir == Dir::North
+            || bot.last_decision().unwrap().reason.ends_with("overruled in favour of North"));
+}
+
+#[test]
+fn ensembles_are_configured_from_json() {
+    let mut config = EnsembleConfig::new();
+    assert_eq!(config.arbitration, Arbitration::Priority(0.8));
+    let value = rustc_serialize::json::Json::from_str(r#"{"arbitration": "vote", "bot_weight": 2.0}"#).unwrap();
+    config = EnsembleConfig::from_json(&value).unwrap();
+    assert_eq!(config.arbitration, Arbitration::WeightedVote);
+    assert_eq!((config.combat_weight, config.bot_weight), (1.0, 2.0));
+
+    config.arbitration = Arbitration::Gated(Gate::new(2));
+    assert_eq!(EnsembleConfig::from_json(&config.to_json()), Some(config.clone()));
+}
+
+#[test]
+fn the_gate_learns_from_whole_games() {
+    let gate = Gate::new(2);
+    let learned = ensemble::learn_gate(&EnsembleConfig::new(), &gate, &[3, 4], 200);
+    assert_eq!(learned.weights.len(), 2);
+    assert!(learned != gate);
+}
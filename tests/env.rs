@@ -0,0 +1,77 @@
+extern crate vindinium_bot;
+use vindinium_bot::vindinium::Dir;
+use vindinium_bot::env;
+use vindinium_bot::env::{Env, Observation, Policy, PolicyPlayer, LinearPolicy};
+use vindinium_bot::bot::Bot;
+use vindinium_bot::simulator::{Simulator, Player};
+
+struct Still;
+
+impl Policy for Still {
+    fn act(&mut self, _: &Observation) -> Dir {
+        Dir::Stay
+    }
+}
+
+#[test]
+fn observations_have_a_fixed_size() {
+    let sim = Simulator::new(5);
+    let state = sim.state_for(2);
+    let observation = Observation::encode(&state);
+    assert_eq!(observation.data.len(), env::OBSERVATION_LEN);
+
+    let pos = &state.hero.pos;
+    assert_eq!(observation.data[Observation::index(5, pos.x as usize, pos.y as usize)], 1.0);
+    // everything past the board is padded as wood
+    let size = state.game.board.size;
+    assert_eq!(observation.data[Observation::index(0, size, 0)], 1.0);
+    assert_eq!(observation.data[Observation::index(0, env::GRID - 1, env::GRID - 1)], 1.0);
+}
+
+#[test]
+fn episodes_run_until_done() {
+    let mut env = Env::new(2);
+    env.set_turns(40);
+    let first = env.reset(9);
+    assert_eq!(first, env.reset(9));
+    assert_eq!(env.state().game.turn, 1);
+
+    let mut steps = 0;
+    loop {
+        let (observation, reward, done) = env.step(Dir::Stay);
+        assert_eq!(observation.data.len(), env::OBSERVATION_LEN);
+        assert!(reward >= 0.0);
+        steps += 1;
+        if done {
+            break;
+        }
+    }
+    assert_eq!(steps, 10);
+}
+
+#[test]
+fn policies_plug_in_as_players() {
+    let sim = Simulator::new(5);
+    let mut player = PolicyPlayer {policy: Still};
+    assert_eq!(player.play(&sim.state_for(1)), Dir::Stay);
+}
+
+#[test]
+fn trained_policies_load_into_the_bot() {
+    // only the bias of the second row, North, is set
+    let mut weights = vec![vec![0.0; env::OBSERVATION_LEN + 1]; 5];
+    weights[1][env::OBSERVATION_LEN] = 1.0;
+    let policy = LinearPolicy {weights: weights};
+    let filename = "test-policy.json";
+    policy.save(filename).unwrap();
+    let loaded = LinearPolicy::load(filename);
+    ::std::fs::remove_file(filename).unwrap();
+    assert_eq!(loaded, Some(policy.clone()));
+
+    let sim = Simulator::new(5);
+    let state = sim.state_for(1);
+    let mut bot = Bot::new();
+    bot.set_policy(Box::new(policy));
+    bot.step(&state);
+    assert_eq!(bot.last_decision().unwrap().reason, "move of the trained policy");
+}
@@ -0,0 +1,38 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::vindinium::{State, Pos};
+use vindinium_bot::pathing;
+use vindinium_bot::pathing::{Map, UVector2};
+use vindinium_bot::influence::InfluenceMap;
+use vindinium_bot::evade;
+use common::{load_state, place_hero};
+
+/// Frees hero 1 from its walled-in corner and puts it two moves from us
+fn with_hunter(life: isize) -> State {
+    let mut state = load_state();
+    place_hero(&mut state, 1, Pos {x: 6, y: 8}).life = life;
+    state
+}
+
+#[test]
+fn runs_from_stronger_enemy() {
+    let state = with_hunter(100);
+    let map = Map::from(&state.game.board);
+    let ours = pathing::distance_map(&UVector2::from(&state.hero.pos), &map);
+
+    let threats = evade::threats(&state, &ours);
+    assert_eq!(threats.len(), 1);
+    assert_eq!(threats[0].id, 1);
+
+    let escape = evade::escape(&state, &map, &InfluenceMap::build(&state, &map)).unwrap();
+    assert!(escape.margin > 0);
+    assert!(ours[escape.target.x][escape.target.y].unwrap() > 0);
+}
+
+#[test]
+fn stays_put_against_weaker_enemy() {
+    let mut state = with_hunter(10);
+    state.hero.life = 100;
+    let map = Map::from(&state.game.board);
+    assert!(evade::escape(&state, &map, &InfluenceMap::build(&state, &map)).is_none());
+}
@@ -0,0 +1,55 @@
+extern crate vindinium_bot;
+mod common;
+use std::env;
+use std::fs;
+use vindinium_bot::vindinium::{Dir};
+use vindinium_bot::pathing::Map;
+use vindinium_bot::influence::InfluenceMap;
+use vindinium_bot::features;
+use vindinium_bot::evaluator::Evaluator;
+use common::load_state;
+
+#[test]
+fn features_describe_each_move() {
+    let mut state = load_state();
+    state.hero.life = 100;
+    let map = Map::from(&state.game.board);
+    let influence = InfluenceMap::build(&state, &map);
+    let all = features::extract_all(&state, &map, &influence);
+    assert_eq!(all.len(), 5);
+    for &(_, ref f) in &all {
+        assert_eq!(f.len(), features::FEATURE_COUNT);
+        assert_eq!(f[0], 1.0);
+    }
+
+    let captures = features::NAMES.iter().position(|name| *name == "captures").unwrap();
+    // the mine at (3,7) is ours, so walking north onto (3,8) captures nothing
+    let north = &all.iter().find(|&&(ref dir, _)| *dir == Dir::North).unwrap().1;
+    assert_eq!(north[captures], 0.0);
+
+    state.game.board.tiles[3][8] = vindinium_bot::vindinium::Tile::Mine(None);
+    let map = Map::from(&state.game.board);
+    let north = features::extract(&state, &map, &influence, &Dir::North);
+    assert_eq!(north[captures], 1.0);
+    assert_eq!(Evaluator::new().best(&state, &map, &influence), Dir::North);
+}
+
+#[test]
+fn evaluators_load_from_files() {
+    let path = env::temp_dir().join("vindinium_bot_evaluator_test.json");
+    let filename = path.to_str().unwrap();
+
+    let linear = Evaluator::new();
+    linear.save(filename).unwrap();
+    assert_eq!(Evaluator::load(filename), Some(linear));
+
+    let mlp = Evaluator::Mlp {hidden: vec!(vec![0.5; features::FEATURE_COUNT + 1]), output: vec!(2.0, -1.0)};
+    mlp.save(filename).unwrap();
+    let loaded = Evaluator::load(filename).unwrap();
+    fs::remove_file(filename).unwrap();
+
+    let inputs = vec![0.1; features::FEATURE_COUNT];
+    assert!((loaded.score(&inputs) - mlp.score(&inputs)).abs() < 1e-6);
+    let expected = 2.0 * (0.5 * 0.1 * features::FEATURE_COUNT as f32 + 0.5).tanh() - 1.0;
+    assert!((mlp.score(&inputs) - expected).abs() < 1e-6);
+}
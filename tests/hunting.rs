@@ -0,0 +1,47 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::vindinium::{State, Pos};
+use vindinium_bot::pathing::{Map};
+use vindinium_bot::hunting;
+use vindinium_bot::mines;
+use common::{load_state, place_hero};
+
+/// Hero 1 two moves from us, as prey
+fn with_prey(life: isize, mine_count: isize) -> State {
+    let mut state = load_state();
+    {
+        let prey = place_hero(&mut state, 1, Pos {x: 6, y: 8});
+        prey.life = life;
+        prey.mine_count = mine_count;
+    }
+    state
+}
+
+#[test]
+fn weak_rich_enemy_is_worth_hunting() {
+    let state = with_prey(10, 3);
+    let bounties = hunting::rank(&state, &Map::from(&state.game.board));
+    assert_eq!(bounties.len(), 1);
+    assert_eq!(bounties[0].hero_id, 1);
+    assert_eq!(bounties[0].mines, 3);
+    assert_eq!(bounties[0].steps, 2);
+    assert!(bounties[0].gold > 0.0);
+}
+
+#[test]
+fn looted_mines_are_not_held_forever() {
+    let mut state = with_prey(10, 3);
+    state.game.turn = 0;
+    let bounties = hunting::rank(&state, &Map::from(&state.game.board));
+    assert!(bounties[0].gold > 0.0);
+    assert!(bounties[0].gold <= 3.0 * mines::BASE_HOLD);
+}
+
+#[test]
+fn unwinnable_or_poor_enemies_are_skipped() {
+    let state = with_prey(100, 3);
+    assert!(hunting::rank(&state, &Map::from(&state.game.board)).is_empty());
+
+    let state = with_prey(10, 0);
+    assert!(hunting::rank(&state, &Map::from(&state.game.board)).is_empty());
+}
@@ -0,0 +1,33 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::pathing::{Map, UVector2};
+use vindinium_bot::influence::InfluenceMap;
+use common::load_state;
+
+#[test]
+fn crashed_heroes_are_harmless() {
+    let state = load_state();
+    let influence = InfluenceMap::build(&state, &Map::from(&state.game.board));
+    assert!(influence.danger.iter().all(|row| row.iter().all(|d| *d == 0.0)));
+}
+
+#[test]
+fn danger_fades_with_distance_from_stronger_enemy() {
+    let mut state = load_state();
+    state.game.heroes[0].crashed = false;
+    state.game.heroes[0].life = 100;
+    let map = Map::from(&state.game.board);
+    let influence = InfluenceMap::build(&state, &map);
+
+    let enemy = UVector2::from(&state.game.heroes[0].pos);
+    let next_to = UVector2 {x: enemy.x, y: enemy.y - 1};
+    let far = UVector2 {x: 13, y: 8};
+    assert!(influence.danger_at(&next_to) >= 1.0);
+    assert!(influence.danger_at(&next_to) > influence.danger_at(&UVector2 {x: enemy.x + 3, y: enemy.y}));
+    assert_eq!(influence.danger_at(&far), 0.0);
+
+    let mut costly = map.clone();
+    influence.apply_costs(&mut costly);
+    assert!(costly.grid[next_to.x][next_to.y].cost > 0);
+    assert_eq!(costly.grid[far.x][far.y].cost, 0);
+}
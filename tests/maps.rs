@@ -0,0 +1,92 @@
+extern crate vindinium_bot;
+mod common;
+use std::env;
+use std::fs;
+use vindinium_bot::vindinium::{Board, Tile, Dir};
+use vindinium_bot::pathing::UVector2;
+use vindinium_bot::maps;
+use vindinium_bot::maps::{KnownMaps, Symmetry};
+use common::load_state;
+
+fn turned(board: &Board, symmetry: Symmetry) -> Board {
+    let mut tiles = board.tiles.clone();
+    for (x, row) in board.tiles.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            let to = symmetry.apply(&UVector2 {x: x, y: y}, board.size);
+            tiles[to.x][to.y] = tile.clone();
+        }
+    }
+    Board {size: board.size, tiles: tiles}
+}
+
+#[test]
+fn fingerprints_ignore_orientation_owners_and_heroes() {
+    let state = load_state();
+    let board = &state.game.board;
+    let print = maps::fingerprint(board);
+    for s in 0..8 {
+        assert_eq!(maps::fingerprint(&turned(board, Symmetry(s))), print);
+    }
+
+    let mut other = board.clone();
+    other.tiles[3][7] = Tile::Mine(None);
+    other.tiles[4][8] = Tile::Free;
+    other.tiles[3][8] = Tile::Hero(4);
+    assert_eq!(maps::fingerprint(&other), print);
+
+    other.tiles[3][8] = Tile::Wood;
+    assert!(maps::fingerprint(&other) != print);
+}
+
+#[test]
+fn symmetries_undo_themselves() {
+    let pos = UVector2 {x: 3, y: 11};
+    for s in 0..8 {
+        let symmetry = Symmetry(s);
+        assert_eq!(symmetry.undo(&symmetry.apply(&pos, 18), 18), pos);
+        for dir in vec!(Dir::North, Dir::South, Dir::East, Dir::West, Dir::Stay) {
+            assert_eq!(symmetry.undo_dir(&symmetry.apply_dir(&dir)), dir);
+        }
+    }
+    assert_eq!(Symmetry(1).apply_dir(&Dir::North), Dir::South);
+    assert_eq!(Symmetry(4).apply_dir(&Dir::North), Dir::West);
+}
+
+#[test]
+fn known_maps_are_named_and_counted() {
+    let state = load_state();
+    let board = &state.game.board;
+    let mut known = KnownMaps::new();
+    assert_eq!(known.identify(board), None);
+    assert_eq!(known.recognize(board, None), "arena-1");
+    // playing it in training tells us which official map it is
+    assert_eq!(known.recognize(&turned(board, Symmetry(6)), Some("m3")), "m3");
+    assert_eq!(known.recognize(board, None), "m3");
+    assert_eq!(known.identify(board).unwrap().games, 3);
+
+    let path = env::temp_dir().join("vindinium_bot_maps_test.json");
+    let filename = path.to_str().unwrap();
+    known.save(filename).unwrap();
+    let loaded = KnownMaps::load(filename).unwrap();
+    fs::remove_file(filename).unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded.identify(board), known.identify(board));
+}
+
+#[test]
+fn arena_names_are_never_reused() {
+    let state = load_state();
+    let first = &state.game.board;
+    let mut second = first.clone();
+    second.tiles[0][0] = Tile::Tavern;
+    let mut third = first.clone();
+    third.tiles[0][1] = Tile::Tavern;
+
+    let mut known = KnownMaps::new();
+    assert_eq!(known.recognize(first, None), "arena-1");
+    assert_eq!(known.recognize(&second, None), "arena-2");
+    // renaming the first arena map frees no number for the next one
+    assert_eq!(known.recognize(first, Some("m1")), "m1");
+    assert_eq!(known.recognize(&third, None), "arena-3");
+    assert_eq!(known.recognize(&second, None), "arena-2");
+}
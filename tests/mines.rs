@@ -0,0 +1,44 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::vindinium::{Tile};
+use vindinium_bot::pathing;
+use vindinium_bot::pathing::{Map, UVector2};
+use vindinium_bot::mines;
+use vindinium_bot::mines::MineLedger;
+use common::load_state;
+
+#[test]
+fn ledger_counts_ownership_changes() {
+    let mut state = load_state();
+    let mut ledger = MineLedger::new();
+    ledger.observe(&state);
+
+    let mine = UVector2 {x: 3, y: 7};
+    assert_eq!(state.game.board.tiles[mine.x][mine.y], Tile::Mine(Some(4)));
+    state.game.board.tiles[mine.x][mine.y] = Tile::Mine(Some(1));
+    state.game.turn += 4;
+    ledger.observe(&state);
+
+    let record = ledger.get(&mine).unwrap();
+    assert_eq!(record.owner, Some(1));
+    assert_eq!(record.flips, 1);
+    assert_eq!(record.last_flip, Some(state.game.turn));
+    assert_eq!(ledger.flip_rate(&UVector2 {x: 3, y: 10}), 0.0);
+}
+
+#[test]
+fn only_reachable_foreign_mines_are_valued() {
+    let state = load_state();
+    let hero_pos = UVector2::from(&state.hero.pos);
+    let map = Map::from(&state.game.board);
+    let distances = pathing::distance_map(&hero_pos, &map);
+    assert_eq!(distances[hero_pos.x][hero_pos.y], Some(0));
+    assert_eq!(distances[0][0], None);
+
+    let values = mines::evaluate(&state, &MineLedger::new(), &distances);
+    assert!(!values.is_empty());
+    for value in &values {
+        assert!(value.owner != Some(state.hero.id));
+        assert_eq!(distances[value.pos.x][value.pos.y], Some(value.steps));
+    }
+}
@@ -0,0 +1,110 @@
+extern crate vindinium_bot;
+mod common;
+use std::env;
+use std::fs;
+use vindinium_bot::vindinium::{Tile, Dir};
+use vindinium_bot::pathing::UVector2;
+use vindinium_bot::opening;
+use vindinium_bot::opening::{OpeningBook, Line};
+use vindinium_bot::simulator::Simulator;
+use vindinium_bot::bot::Bot;
+use common::load_state;
+
+fn book_line(score: f32) -> Line {
+    Line {
+        moves: vec!(Dir::North, Dir::East),
+        positions: vec!(UVector2 {x: 4, y: 8}, UVector2 {x: 3, y: 8}),
+        score: score
+    }
+}
+
+#[test]
+fn book_plays_recorded_lines_while_on_track() {
+    let mut state = load_state();
+    state.game.turn = 0;
+    let mut book = OpeningBook::new();
+    assert_eq!(book.next_move(&state), None);
+
+    assert!(book.record(&state, book_line(500.0)));
+    assert!(!book.record(&state, book_line(400.0)));
+    assert_eq!(book.len(), 1);
+    assert_eq!(book.next_move(&state), Some(Dir::North));
+
+    // one of our moves later we should be on (3,8), but we are not
+    state.game.turn = 4;
+    assert_eq!(book.next_move(&state), None);
+    state.hero.pos.x = 3;
+    assert_eq!(book.next_move(&state), Some(Dir::East));
+
+    // another spawn on the same map has no line yet
+    state.hero.spawn_pos.y = 6;
+    assert_eq!(book.next_move(&state), None);
+}
+
+#[test]
+fn book_lines_follow_the_map_when_it_is_turned() {
+    let mut state = load_state();
+    state.game.turn = 0;
+    // the map is symmetric, so its mirror image would just be another spawn on it
+    state.game.board.tiles[16][7] = Tile::Wood;
+    let mut book = OpeningBook::new();
+    book.record(&state, book_line(500.0));
+
+    // the same map mirrored left to right, with everyone standing on the mirrored tiles
+    let size = state.game.board.size;
+    let mut mirrored = state.clone();
+    for row in mirrored.game.board.tiles.iter_mut() {
+        row.reverse();
+    }
+    mirrored.hero.pos.y = (size - 1) as isize - state.hero.pos.y;
+    mirrored.hero.spawn_pos.y = (size - 1) as isize - state.hero.spawn_pos.y;
+    assert_eq!(book.next_move(&mirrored), Some(Dir::North));
+    mirrored.game.turn = 4;
+    mirrored.hero.pos.x = 3;
+    assert_eq!(book.next_move(&mirrored), Some(Dir::West));
+}
+
+#[test]
+fn books_load_from_files() {
+    let mut state = load_state();
+    state.game.turn = 0;
+    let mut book = OpeningBook::new();
+    book.record(&state, book_line(500.0));
+
+    let path = env::temp_dir().join("vindinium_bot_opening_test.json");
+    let filename = path.to_str().unwrap();
+    book.save(filename).unwrap();
+    let loaded = OpeningBook::load(filename).unwrap();
+    fs::remove_file(filename).unwrap();
+    assert_eq!(loaded.line(&state), Some(book_line(500.0)));
+}
+
+#[test]
+fn search_finds_an_opening_from_the_spawn() {
+    let sim = Simulator::new(3);
+    let line = opening::search(&sim.game, 1, 10);
+    assert_eq!(line.moves.len(), 10);
+    assert_eq!(line.positions.len(), 10);
+    assert_eq!(line.positions[0], UVector2::from(&sim.game.heroes[0].pos));
+    assert!(line.score > 0.0);
+}
+
+#[test]
+fn played_openings_are_scored_like_searched_ones() {
+    let mut sim = Simulator::new(3);
+    sim.game.max_turns = 200;
+    let mut bots = (0..4).map(|_| Bot::new()).collect::<Vec<Bot>>();
+    let mut w_projected = None;
+    while !sim.finished() {
+        let id = sim.to_move();
+        let state = sim.state_for(id);
+        if id == 1 && opening::own_move(&state) == opening::BOOK_TURNS {
+            w_projected = Some(opening::projected_gold(&state, 1));
+        }
+        let dir = bots[id as usize - 1].step(&state);
+        sim.step(&dir);
+    }
+    let state = sim.state_for(1);
+    assert!(bots[0].finish(&state));
+    assert_eq!(bots[0].book().line(&state).map(|line| line.score), w_projected);
+}
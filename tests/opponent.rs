@@ -0,0 +1,32 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::vindinium::{Dir};
+use vindinium_bot::opponent::{OpponentModel, Behavior};
+use common::load_state;
+
+#[test]
+fn crashed_heroes_are_expected_to_stay() {
+    let state = load_state();
+    let mut model = OpponentModel::new();
+    model.observe(&state);
+    model.observe(&state);
+
+    let profile = model.profile(1).unwrap();
+    assert_eq!(profile.behavior(), Behavior::Crashed);
+    assert!(profile.probability(&Behavior::Crashed) > 0.99);
+    assert_eq!(model.likely_move(&state, 1), Dir::Stay);
+}
+
+#[test]
+fn predictions_are_distributions() {
+    let mut state = load_state();
+    state.hero = state.game.heroes[0].clone();
+    let mut model = OpponentModel::new();
+    model.observe(&state);
+
+    let prediction = model.predict(&state, 4);
+    assert_eq!(prediction.len(), 5);
+    let total: f32 = prediction.iter().map(|&(_, p)| p).sum();
+    assert!((total - 1.0).abs() < 1e-4);
+    assert!(model.profile(state.hero.id).is_none());
+}
@@ -0,0 +1,45 @@
+extern crate vindinium_bot;
+extern crate rustc_serialize;
+use std::env;
+use std::fs;
+use vindinium_bot::params;
+use vindinium_bot::params::Params;
+
+#[test]
+fn vectors_are_clamped_to_bounds() {
+    let defaults = Params::new();
+    assert_eq!(Params::from_vec(&defaults.to_vec()), defaults);
+
+    let wild = Params::from_vec(&[-1.0, 100.0, 0.5, 0.25]);
+    assert_eq!(wild.low_health_per, params::BOUNDS[0].0);
+    assert_eq!(wild.close_radius, params::BOUNDS[1].1 as usize);
+    assert_eq!(wild.farm_bonus, defaults.farm_bonus);
+    assert!(!wild.exploit_telefrag);
+    assert!(wild.camp_when_leading);
+    assert!(Params::from_vec(&[0.25, 3.0, 0.5, 0.25, 0.5, 0.8]).exploit_telefrag);
+    assert!(!Params::from_vec(&[0.25, 3.0, 0.5, 0.25, 0.5, 0.0, 0.2]).camp_when_leading);
+}
+
+#[test]
+fn params_survive_a_round_trip_through_a_file() {
+    let path = env::temp_dir().join("vindinium_bot_params_test.json");
+    let filename = path.to_str().unwrap();
+    let tuned = Params::from_vec(&[0.4, 5.0, 0.75, 1.0, 0.0]);
+    tuned.save(filename).unwrap();
+    let loaded = Params::load(filename).unwrap();
+    fs::remove_file(filename).unwrap();
+
+    assert_eq!(loaded.close_radius, 5);
+    for (a, b) in loaded.to_vec().iter().zip(tuned.to_vec()) {
+        assert!((a - b).abs() < 1e-6);
+    }
+    assert!(Params::load(filename).is_none());
+}
+
+#[test]
+fn switches_load_from_booleans() {
+    let value = rustc_serialize::json::Json::from_str(r#"{"camp_when_leading": false, "exploit_telefrag": true}"#).unwrap();
+    let params = Params::from_json(&value).unwrap();
+    assert!(!params.camp_when_leading);
+    assert!(params.exploit_telefrag);
+}
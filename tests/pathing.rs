@@ -99,4 +99,63 @@ fn display_path() {
         None => {panic!("Error occurred while computing path.");}
     };
     print_over(&path, &map);
-}
\ No newline at end of file
+}
+
+#[test]
+fn blocked_cells_are_avoided() {
+    let mut json_str = String::new();
+    let res = File::open(&Path::new("tests/test_state.json")).unwrap().read_to_string(&mut json_str);
+    match res {
+        Ok(_) => {}
+        Err(err) => panic!("{}", err)
+    }
+
+    let state: State = match json::decode(&json_str) {
+        Ok(state) => state,
+        Err(err) => panic!("{}", err)
+    };
+
+    let mut map: pathing::Map = pathing::Map::from(&state.game.board);
+    let player_pos = pathing::UVector2::from(&state.hero.pos);
+    let target = pathing::UVector2 { x: player_pos.x + 8, y: player_pos.y };
+    let first_step = match pathing::gen_path(&player_pos, &target, &map) {
+        Some(path) => path.front().unwrap().pos.clone(),
+        None => panic!("Error occurred while computing path.")
+    };
+
+    map.block(&first_step);
+    match pathing::gen_path(&player_pos, &target, &map) {
+        Some(path) => assert!(path.iter().all(|cell| cell.pos != first_step)),
+        None => panic!("No path around the blocked cell.")
+    }
+    map.block(&target);
+    assert!(pathing::gen_path(&player_pos, &target, &map).is_none());
+}
+
+#[test]
+fn crashed_heroes_are_walls() {
+    let mut json_str = String::new();
+    let res = File::open(&Path::new("tests/test_state.json")).unwrap().read_to_string(&mut json_str);
+    match res {
+        Ok(_) => {}
+        Err(err) => panic!("{}", err)
+    }
+
+    let mut state: State = match json::decode(&json_str) {
+        Ok(state) => state,
+        Err(err) => panic!("{}", err)
+    };
+
+    let player_pos = pathing::UVector2::from(&state.hero.pos);
+    let crashed_pos = pathing::UVector2::from(&state.game.heroes[0].pos);
+    let mut map: pathing::Map = pathing::Map::from(&state.game.board);
+    assert!(pathing::gen_path(&player_pos, &crashed_pos, &map).is_some());
+
+    map.block_crashed(&state.game.heroes);
+    assert!(pathing::gen_path(&player_pos, &crashed_pos, &map).is_none());
+
+    state.game.heroes[0].crashed = false;
+    let mut map: pathing::Map = pathing::Map::from(&state.game.board);
+    map.block_crashed(&state.game.heroes);
+    assert!(pathing::gen_path(&player_pos, &crashed_pos, &map).is_some());
+}
@@ -0,0 +1,48 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::vindinium::{State};
+use vindinium_bot::pathing;
+use vindinium_bot::pathing::{Map, UVector2};
+use vindinium_bot::mines;
+use vindinium_bot::mines::MineLedger;
+use vindinium_bot::route;
+use vindinium_bot::route::StopKind;
+use common::load_state;
+
+fn plan_route(state: &State) -> Option<route::Route> {
+    let map = Map::from(&state.game.board);
+    let distances = pathing::distance_map(&UVector2::from(&state.hero.pos), &map);
+    let candidates = mines::evaluate(state, &MineLedger::new(), &distances);
+    route::plan(state, &map, &distances, &candidates)
+}
+
+#[test]
+fn route_visits_mines_in_turn_order() {
+    let mut state = load_state();
+    state.hero.life = 100;
+    state.game.turn = 0;
+    let route = plan_route(&state).unwrap();
+
+    assert!(route.stops.iter().filter(|stop| stop.kind == StopKind::Mine).count() >= 2);
+    for pair in route.stops.windows(2) {
+        assert!(pair[0].arrival < pair[1].arrival);
+    }
+    assert_eq!(route.turns, route.stops.last().unwrap().arrival);
+}
+
+#[test]
+fn low_life_route_stops_at_tavern_first() {
+    let mut state = load_state();
+    state.game.turn = 0;
+    state.hero.life = 100;
+    let first_mine = plan_route(&state).unwrap().stops[0].clone();
+
+    state.hero.life = 22;
+    match plan_route(&state) {
+        Some(route) => {
+            assert_eq!(route.stops[0].kind, StopKind::Tavern);
+            assert!(route.stops.iter().any(|stop| stop.pos == first_mine.pos));
+        },
+        None => panic!("no route despite gold for a tavern")
+    }
+}
@@ -0,0 +1,52 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::vindinium::{Tile, Pos, Dir};
+use vindinium_bot::pathing::{Map};
+use vindinium_bot::safety;
+use vindinium_bot::trace::Decision;
+use common::{load_state, place_hero};
+
+#[test]
+fn walking_into_stronger_enemy_is_vetoed() {
+    let mut state = load_state();
+    place_hero(&mut state, 1, Pos {x: 6, y: 8}).life = 100;
+    let map = Map::from(&state.game.board);
+
+    assert!(!safety::is_safe(&state, &map, &Dir::South, false));
+    let dir = safety::filter(&state, &map, Dir::South, false);
+    assert!(dir != Dir::South);
+    assert!(safety::is_safe(&state, &map, &dir, false));
+}
+
+#[test]
+fn lethal_mine_capture_is_vetoed() {
+    let mut state = load_state();
+    state.game.board.tiles[3][8] = Tile::Mine(None);
+    state.hero.life = 20;
+    let map = Map::from(&state.game.board);
+
+    assert!(safety::is_safe(&state, &map, &Dir::Stay, false));
+    let outcome = safety::simulate(&state, &map, &Dir::North, false);
+    assert!(outcome.fatal);
+    assert!(safety::filter(&state, &map, Dir::North, false) != Dir::North);
+
+    let mut decision = Decision::new(&state);
+    safety::filter_noted(&state, &map, Dir::Stay, false, &mut decision);
+    assert_eq!(decision.vetoed, None);
+    safety::filter_noted(&state, &map, Dir::North, false, &mut decision);
+    assert_eq!(decision.vetoed, Some(Dir::North));
+    assert!(format!("{}", decision).contains("vetoed North as fatal"));
+}
+
+#[test]
+fn dying_onto_a_camper_is_only_safe_when_exploited() {
+    // a lethal mine North of us, and a rich enemy sitting on our spawn
+    let mut state = load_state();
+    state.hero.life = 15;
+    state.game.board.tiles[3][8] = Tile::Mine(None);
+    place_hero(&mut state, 2, Pos {x: 5, y: 11}).mine_count = 8;
+    let map = Map::from(&state.game.board);
+
+    assert!(safety::simulate(&state, &map, &Dir::North, false).fatal);
+    assert!(!safety::simulate(&state, &map, &Dir::North, true).fatal);
+}
@@ -0,0 +1,80 @@
+extern crate vindinium_bot;
+use vindinium_bot::vindinium::{Tile, Dir, Pos};
+use vindinium_bot::pathing::{UVector2};
+use vindinium_bot::bot::Bot;
+use vindinium_bot::simulator;
+use vindinium_bot::simulator::Simulator;
+
+fn free_neighbor(sim: &Simulator, pos: &UVector2, tile: &Tile) -> Option<(UVector2, Dir)> {
+    let size = sim.game.board.size;
+    for dir in vec!(Dir::North, Dir::South, Dir::East, Dir::West) {
+        let (dx, dy) = dir.offset();
+        let (x, y) = (pos.x as isize + dx, pos.y as isize + dy);
+        if x < 0 || y < 0 || x >= size as isize || y >= size as isize {
+            continue;
+        }
+        if sim.game.board.tiles[x as usize][y as usize] == *tile {
+            return Some((UVector2 {x: x as usize, y: y as usize}, dir))
+        }
+    }
+    None
+}
+
+#[test]
+fn maps_are_symmetric_and_repeatable() {
+    let sim = Simulator::new(7);
+    let again = Simulator::new(7);
+    let size = sim.game.board.size;
+    assert_eq!(sim.game.board.tiles, again.game.board.tiles);
+    assert_eq!(sim.game.heroes.len(), 4);
+    for x in 0..size {
+        for y in 0..size {
+            match sim.game.board.tiles[x][y] {
+                Tile::Hero(_) => {},
+                ref tile => assert_eq!(*tile, sim.game.board.tiles[size - 1 - x][size - 1 - y])
+            }
+        }
+    }
+}
+
+#[test]
+fn capturing_a_mine_costs_life() {
+    let mut sim = Simulator::new(3);
+    sim.game.heroes[0].life = 100;
+    // put hero 1 next to a mine and walk into it
+    let mut mine_pos = None;
+    for x in 0..sim.game.board.size {
+        for y in 0..sim.game.board.size {
+            if sim.game.board.tiles[x][y] == Tile::Mine(None)
+                && free_neighbor(&sim, &UVector2 {x: x, y: y}, &Tile::Free).is_some() {
+                mine_pos = Some(UVector2 {x: x, y: y});
+            }
+        }
+    }
+    let mine = mine_pos.unwrap();
+    let (stand, _) = free_neighbor(&sim, &mine, &Tile::Free).unwrap();
+    let old = UVector2::from(&sim.game.heroes[0].pos);
+    sim.game.board.tiles[old.x][old.y] = Tile::Free;
+    sim.game.board.tiles[stand.x][stand.y] = Tile::Hero(1);
+    sim.game.heroes[0].pos = Pos {x: stand.x as isize, y: stand.y as isize};
+    let (_, dir) = free_neighbor(&sim, &stand, &Tile::Mine(None)).unwrap();
+
+    sim.step(&dir);
+    assert_eq!(sim.game.board.tiles[mine.x][mine.y], Tile::Mine(Some(1)));
+    assert_eq!(sim.game.heroes[0].mine_count, 1);
+    assert_eq!(sim.game.heroes[0].gold, 1);
+    assert_eq!(sim.game.heroes[0].life, 79);
+    assert_eq!(sim.to_move(), 2);
+}
+
+#[test]
+fn bots_play_a_short_game_to_the_end() {
+    let mut sim = Simulator::new(11);
+    sim.game.max_turns = 40;
+    let mut players = vec!(Bot::new(), Bot::new(), Bot::new(), Bot::new());
+    let heroes = sim.run(&mut players);
+    assert!(sim.finished());
+    assert_eq!(sim.game.turn, 40);
+    let ranks = heroes.iter().map(|hero| simulator::rank(&heroes, hero.id)).collect::<Vec<usize>>();
+    assert!(ranks.contains(&1));
+}
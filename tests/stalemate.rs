@@ -0,0 +1,57 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::pathing::{UVector2};
+use vindinium_bot::stalemate::{MotionTracker, Stuck};
+use common::load_state;
+
+#[test]
+fn standing_still_for_nothing_is_a_stall() {
+    let state = load_state();
+    let mut tracker = MotionTracker::new();
+    for _ in 0..4 {
+        tracker.observe(&state);
+        assert_eq!(tracker.check(), None);
+    }
+    tracker.observe(&state);
+    assert_eq!(tracker.check(), Some(Stuck::Stalled));
+
+    tracker.ban(&UVector2 {x: 3, y: 7}, state.game.turn);
+    assert_eq!(tracker.check(), None);
+    assert_eq!(tracker.banned(), vec!(UVector2 {x: 3, y: 7}));
+}
+
+#[test]
+fn alternating_goals_are_a_flip_flop() {
+    let a = UVector2 {x: 3, y: 7};
+    let b = UVector2 {x: 14, y: 7};
+    let mut tracker = MotionTracker::new();
+    for goal in vec!(a.clone(), b.clone(), a.clone()) {
+        tracker.record_goal(Some(goal));
+        assert_eq!(tracker.check(), None);
+    }
+    tracker.record_goal(Some(b.clone()));
+    assert_eq!(tracker.check(), Some(Stuck::FlipFlop(b, a)));
+}
+
+#[test]
+fn bouncing_between_two_tiles_is_oscillating() {
+    let mut state = load_state();
+    let mut tracker = MotionTracker::new();
+    for i in 0..8 {
+        state.hero.pos.y = 8 + (i % 2);
+        tracker.observe(&state);
+    }
+    assert_eq!(tracker.check(), Some(Stuck::Oscillating));
+}
+
+#[test]
+fn camping_by_a_tavern_is_not_oscillating() {
+    let mut state = load_state();
+    state.hero.pos.x = 5;
+    state.hero.pos.y = 11;
+    let mut tracker = MotionTracker::new();
+    for _ in 0..8 {
+        tracker.observe(&state);
+        assert_eq!(tracker.check(), None);
+    }
+}
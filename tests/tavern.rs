@@ -0,0 +1,49 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::pathing;
+use vindinium_bot::pathing::{Map, UVector2};
+use vindinium_bot::tavern;
+use common::load_state;
+
+#[test]
+fn heals_stop_before_wasting_gold() {
+    assert_eq!(tavern::heals_needed(30, 100, 60), (1, 79));
+    assert_eq!(tavern::heals_needed(10, 100, 90), (2, 99));
+    assert_eq!(tavern::heals_needed(10, 3, 90), (1, 59));
+    assert_eq!(tavern::heals_needed(10, 1, 90), (0, 10));
+    assert_eq!(tavern::heals_needed(80, 100, 60), (0, 80));
+}
+
+#[test]
+fn plan_needs_gold_and_a_path() {
+    let mut state = load_state();
+    let hero_pos = UVector2::from(&state.hero.pos);
+    let map = Map::from(&state.game.board);
+    let distances = pathing::distance_map(&hero_pos, &map);
+
+    let plan = tavern::plan(&state, &map, &distances, 90).unwrap();
+    assert_eq!(distances[plan.pos.x][plan.pos.y], Some(plan.steps));
+    assert_eq!(plan.life_on_arrival, tavern::life_after_walk(state.hero.life, plan.steps - 1));
+    assert!(plan.heals > 0);
+
+    state.hero.gold = 1;
+    assert!(tavern::plan(&state, &map, &distances, 90).is_none());
+}
+
+#[test]
+fn no_heals_are_bought_that_the_game_has_no_time_for() {
+    let mut state = load_state();
+    state.hero.life = 10;
+    let hero_pos = UVector2::from(&state.hero.pos);
+    let map = Map::from(&state.game.board);
+    let distances = pathing::distance_map(&hero_pos, &map);
+    let steps = tavern::plan(&state, &map, &distances, 90).unwrap().steps as isize;
+
+    // one move left once we get there buys one heal
+    state.game.turn = state.game.max_turns - (steps + 1) * 4;
+    let plan = tavern::plan(&state, &map, &distances, 90).unwrap();
+    assert_eq!(plan.heals, 1);
+
+    state.game.turn = state.game.max_turns - steps * 4;
+    assert!(tavern::plan(&state, &map, &distances, 90).is_none());
+}
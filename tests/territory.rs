@@ -0,0 +1,45 @@
+extern crate vindinium_bot;
+mod common;
+use vindinium_bot::pathing;
+use vindinium_bot::pathing::{Map, UVector2};
+use vindinium_bot::territory::Territory;
+use common::load_state;
+
+#[test]
+fn lone_hero_owns_everything_it_reaches() {
+    let state = load_state();
+    let territory = Territory::analyze(&state, &Map::from(&state.game.board));
+    let ours = territory.summary(4).unwrap();
+    assert!(ours.tiles > 0);
+    assert!(!ours.mines.is_empty());
+    assert!(ours.contested_mines.is_empty());
+    assert_eq!(territory.summary(1).unwrap().tiles, 0);
+}
+
+#[test]
+fn turn_order_breaks_ties() {
+    let mut state = load_state();
+    state.game.heroes[1].crashed = false;
+    let map = Map::from(&state.game.board);
+    let from_second = pathing::distance_map(&UVector2::from(&state.game.heroes[1].pos), &map);
+    let from_us = pathing::distance_map(&UVector2::from(&state.hero.pos), &map);
+
+    let mut w_tie: Option<UVector2> = None;
+    for x in 0..map.size as usize {
+        for y in 0..map.size as usize {
+            match (from_second[x][y], from_us[x][y]) {
+                (Some(a), Some(b)) if a == b && a > 0 => w_tie = Some(UVector2 {x: x, y: y}),
+                _ => {}
+            }
+        }
+    }
+    let tie = w_tie.unwrap();
+
+    // hero 2 moves before us on turn 1100, we move first three turns later
+    let territory = Territory::analyze(&state, &map);
+    assert_eq!(territory.owner_at(&tie), Some(2));
+    assert!(territory.claim_at(&tie).unwrap().contested);
+    state.game.turn = 1103;
+    let territory = Territory::analyze(&state, &map);
+    assert_eq!(territory.owner_at(&tie), Some(4));
+}
@@ -0,0 +1,61 @@
+extern crate vindinium_bot;
+extern crate rustc_serialize;
+mod common;
+use std::env;
+use std::fs;
+use std::string::String;
+use std::fs::File;
+use std::io::Read;
+use vindinium_bot::vindinium::{Dir};
+use vindinium_bot::pathing::UVector2;
+use vindinium_bot::bot::Bot;
+use vindinium_bot::trace;
+use vindinium_bot::trace::{Decision, Candidate};
+use rustc_serialize::json::Json;
+use common::load_state;
+
+#[test]
+fn every_step_is_explained() {
+    let state = load_state();
+    let mut bot = Bot::new();
+    let dir = bot.step(&state);
+    assert_eq!(bot.trace().len(), 1);
+
+    let decision = bot.last_decision().unwrap();
+    assert_eq!(decision.turn, state.game.turn);
+    assert_eq!(decision.hero_id, 4);
+    assert_eq!(decision.dir, dir);
+    assert!(!decision.reason.is_empty());
+    assert!(decision.goal.is_some());
+    assert!(decision.path_length.is_some());
+    assert!(decision.millis >= 0.0);
+}
+
+#[test]
+fn decisions_are_written_as_json_lines() {
+    let state = load_state();
+    let mut decision = Decision::new(&state);
+    assert_eq!(decision.choose(UVector2 {x: 3, y: 8}, "grabbing a close mine"), Some(UVector2 {x: 3, y: 8}));
+    decision.candidates = vec!(Candidate {pos: UVector2 {x: 3, y: 8}, score: 2.5});
+    decision.path_length = Some(1);
+    decision.dir = Dir::North;
+    assert_eq!(format!("{}", decision),
+               "Turn 1100: North toward (3, 8), grabbing a close mine [1 steps, 1 candidates, 0.0 ms]");
+
+    let mut other = Decision::new(&state);
+    other.reason = "nothing worth doing".to_string();
+    let path = env::temp_dir().join("vindinium_bot_trace_test.jsonl");
+    let filename = path.to_str().unwrap();
+    trace::save(filename, &[decision, other]).unwrap();
+    let mut text = String::new();
+    File::open(&path).unwrap().read_to_string(&mut text).unwrap();
+    fs::remove_file(filename).unwrap();
+
+    let lines = text.lines().map(|line| Json::from_str(line).unwrap()).collect::<Vec<Json>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].find("reason").and_then(|r| r.as_string()), Some("grabbing a close mine"));
+    assert_eq!(lines[0].find("dir").and_then(|d| d.as_string()), Some("North"));
+    assert_eq!(lines[0].find("path_length").and_then(|p| p.as_u64()), Some(1));
+    assert_eq!(lines[0].find("candidates").and_then(|c| c.as_array()).map(|c| c.len()), Some(1));
+    assert_eq!(lines[1].find("goal"), Some(&Json::Null));
+}
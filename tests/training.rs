@@ -0,0 +1,44 @@
+extern crate vindinium_bot;
+use std::env;
+use std::fs;
+use vindinium_bot::evaluator::Evaluator;
+use vindinium_bot::training;
+use vindinium_bot::training::{TrainConfig, Version, Checkpoint};
+
+fn config(name: &str) -> TrainConfig {
+    let dir = env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    let mut config = TrainConfig::new();
+    config.iterations = 2;
+    config.games = 1;
+    config.turns = 20;
+    config.dir = dir.to_str().unwrap().to_string();
+    config
+}
+
+#[test]
+fn training_saves_numbered_checkpoints_and_resumes() {
+    let config = config("vindinium_bot_training_test");
+    let mut start = Version::new();
+    start.evaluator = Some(Evaluator::new());
+
+    let mut reported = vec!();
+    let checkpoints = training::train(&config, start.clone(), |checkpoint, _, _| reported.push(checkpoint.number)).unwrap();
+    assert_eq!(reported, vec!(1, 2));
+    assert_eq!(checkpoints.iter().map(|c| c.number).collect::<Vec<usize>>(), vec!(0, 1, 2));
+    assert_eq!(checkpoints[0].version, start);
+
+    let loaded = Checkpoint::load(&Checkpoint::filename(&config.dir, 2)).unwrap();
+    assert_eq!(loaded.version, checkpoints[2].version);
+    assert!((loaded.score - checkpoints[2].score).abs() < 1e-6);
+
+    // a restart carries on numbering from the last checkpoint
+    let resumed = training::train(&config, start.clone(), |_, _, _| {}).unwrap();
+    assert_eq!(resumed.last().unwrap().number, 4);
+    assert_eq!(Checkpoint::list(&config.dir).len(), 5);
+
+    // but not from a run that started elsewhere
+    assert!(training::train(&config, Version::new(), |_, _, _| {}).is_err());
+    assert_eq!(Checkpoint::list(&config.dir).len(), 5);
+    fs::remove_dir_all(&config.dir).unwrap();
+}
@@ -0,0 +1,20 @@
+extern crate vindinium_bot;
+use vindinium_bot::params::Params;
+use vindinium_bot::tuning;
+use vindinium_bot::tuning::TuneConfig;
+
+#[test]
+fn evolution_keeps_the_best_set_it_has_seen() {
+    let mut config = TuneConfig::new();
+    config.population = 3;
+    config.generations = 2;
+    config.games = 1;
+    config.turns = 40;
+    let mut generations = vec!();
+    let best = tuning::evolve(&config, |generation, _| generations.push(generation));
+    assert_eq!(generations, vec!(0, 1));
+    assert!(best.fitness.is_finite());
+    // a single seat's placing is worth at most three points, plus its gold
+    assert!(best.fitness >= 0.0 && best.fitness < 4.0);
+    assert_eq!(Params::from_vec(&best.params.to_vec()), best.params);
+}